@@ -33,6 +33,7 @@
 use anyhow::{bail, Result};
 use clap::Parser;
 use component::{Component, Components};
+use fuelup::http::agent_for;
 use once_cell::sync::Lazy;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -109,7 +110,6 @@ struct Asset {
 }
 
 fn get_version(component: &Component) -> Result<Version> {
-    let handle = ureq::builder().user_agent("fuelup").build();
     let mut data = Vec::new();
 
     let url = format!(
@@ -117,7 +117,7 @@ fn get_version(component: &Component) -> Result<Version> {
         component.repository_name
     );
 
-    let resp = handle.get(&url).call()?;
+    let resp = agent_for(&url)?.get(&url).call()?;
 
     resp.into_reader().read_to_end(&mut data)?;
     let response: LatestReleaseApiResponse = serde_json::from_str(&String::from_utf8_lossy(&data))?;
@@ -156,7 +156,9 @@ fn write_nightly_document(document: &mut Document, components: Vec<Component>) -
         *TODAY
     );
 
-    let resp = ureq::get(&nightly_release_url).call()?;
+    let resp = agent_for(&nightly_release_url)?
+        .get(&nightly_release_url)
+        .call()?;
     resp.into_reader().read_to_end(&mut data)?;
     let release: Release = serde_json::from_str(&String::from_utf8_lossy(&data))?;
 
@@ -216,7 +218,10 @@ fn write_nightly_document(document: &mut Document, components: Vec<Component>) -
                         document["pkg"][&component.name]["target"][target.to_string()]["url"] =
                             value(&asset.browser_download_url);
 
-                        if let Ok(res) = ureq::get(&asset.browser_download_url).call() {
+                        if let Ok(res) = agent_for(&asset.browser_download_url)?
+                            .get(&asset.browser_download_url)
+                            .call()
+                        {
                             res.into_reader().read_to_end(&mut data)?;
                             let mut hasher = Sha256::new();
                             hasher.update(data);
@@ -286,7 +291,7 @@ fn write_document(
                 repo, tag, tarball_prefix, target
             );
 
-            match ureq::get(&url).call() {
+            match agent_for(&url)?.get(&url).call() {
                 Ok(res) => {
                     res.into_reader().read_to_end(&mut data)?;
                     let mut hasher = Sha256::new();
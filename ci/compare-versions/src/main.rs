@@ -16,6 +16,7 @@
 
 use anyhow::{bail, Result};
 use component::Components;
+use fuelup::http::agent_for;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 use std::{io::Read, os::unix::process::CommandExt, process::Command, str::FromStr};
@@ -64,8 +65,7 @@ fn get_workflow_runs(repo: &str) -> Result<WorkflowRunApiResponse> {
         "{}{}/{}?event=release&status=success",
         GITHUB_API_REPOS_BASE_URL, repo, ACTIONS_RUNS
     );
-    let handle = ureq::builder().user_agent("fuelup").build();
-    let resp = handle
+    let resp = agent_for(&github_actions_runs_api_url)?
         .get(&github_actions_runs_api_url)
         .call()
         .unwrap_or_else(|_| panic!("Could not get workflow runs for {}", repo));
@@ -83,8 +83,7 @@ fn get_latest_release_version(repo: &str) -> Result<Version> {
         repo
     );
 
-    let handle = ureq::builder().user_agent("fuelup").build();
-    let response: LatestReleaseApiResponse = match handle.get(&url).call() {
+    let response: LatestReleaseApiResponse = match agent_for(&url)?.get(&url).call() {
         Ok(r) => serde_json::from_reader(r.into_reader())?,
         Err(e) => {
             bail!("Could not get latest release for {}: {}", repo, e);
@@ -157,9 +156,10 @@ fn print_selected_versions(forc_versions: &[Version], fuel_core_versions: &[Vers
 }
 
 fn compare_rest() -> Result<()> {
-    let handle = ureq::builder().user_agent("fuelup").build();
-
-    let toml_resp = match handle.get(CHANNEL_FUEL_LATEST_TOML_URL).call() {
+    let toml_resp = match agent_for(CHANNEL_FUEL_LATEST_TOML_URL)?
+        .get(CHANNEL_FUEL_LATEST_TOML_URL)
+        .call()
+    {
         Ok(r) => r
             .into_string()
             .expect("Could not convert channel to string"),
@@ -229,9 +229,10 @@ fn get_latest_version(repo: &str) -> Result<Version> {
 }
 
 fn compare_compatibility() -> Result<()> {
-    let handle = ureq::builder().user_agent("fuelup").build();
-
-    let toml_resp = match handle.get(CHANNEL_FUEL_LATEST_TOML_URL).call() {
+    let toml_resp = match agent_for(CHANNEL_FUEL_LATEST_TOML_URL)?
+        .get(CHANNEL_FUEL_LATEST_TOML_URL)
+        .call()
+    {
         Ok(r) => r
             .into_string()
             .expect("Could not convert channel to string"),
@@ -4,14 +4,23 @@ use crate::{
         CHANNEL_BETA_4_FILE_NAME, CHANNEL_LATEST_FILE_NAME, CHANNEL_NIGHTLY_FILE_NAME,
         DATE_FORMAT_URL_FRIENDLY, FUELUP_GH_PAGES,
     },
-    download::{download, DownloadCfg},
+    download::{dist_url, download_if_modified, is_offline, Conditional, DownloadCfg},
+    file::{read_file, write_file},
+    path::{channels_dir, ensure_dir_exists},
+    target_triple::TargetTriple,
     toolchain::{DistToolchainDescription, DistToolchainName},
 };
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use component::Components;
 use semver::Version;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt::Debug};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
 use time::Date;
 use toml_edit::de;
 use tracing::warn;
@@ -33,6 +42,10 @@ pub struct HashedBinary {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Channel {
     pub pkg: BTreeMap<String, Package>,
+    /// Where the channel was read from, which is recorded in the manifest of toolchains
+    /// installed from it.
+    #[serde(skip)]
+    pub url: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -77,19 +90,134 @@ fn construct_channel_url(desc: &DistToolchainDescription) -> Result<String> {
         DistToolchainName::Beta4 => url.push_str(CHANNEL_BETA_4_FILE_NAME),
     };
 
-    Ok(url)
+    Ok(dist_url(&url))
+}
+
+/// Validators returned along with a fetched channel, used to revalidate the cached copy.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+struct CachedChannelMeta {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// Channels fetched so far by this process, keyed by URL.
+fn fetched_channels() -> &'static Mutex<HashMap<String, String>> {
+    static FETCHED_CHANNELS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    FETCHED_CHANNELS.get_or_init(Default::default)
+}
+
+/// The paths within `dir` at which a copy of the channel fetched from `url` and its validators
+/// are kept.
+fn cached_channel_paths(dir: &Path, url: &str) -> (PathBuf, PathBuf) {
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    let key = format!("{:x}", hasher.finalize());
+    (
+        dir.join(format!("{key}.toml")),
+        dir.join(format!("{key}.json")),
+    )
+}
+
+fn read_cached_channel(dir: &Path, url: &str) -> Option<(String, CachedChannelMeta)> {
+    let (toml_path, meta_path) = cached_channel_paths(dir, url);
+    let toml = read_file("cached channel", &toml_path).ok()?;
+    let meta = read_file("cached channel metadata", &meta_path)
+        .ok()
+        .and_then(|m| serde_json::from_str::<CachedChannelMeta>(&m).ok())
+        .filter(|m| m.url == url)
+        .unwrap_or_default();
+
+    Some((toml, meta))
+}
+
+fn write_cached_channel(dir: &Path, toml: &str, meta: &CachedChannelMeta) -> Result<()> {
+    let (toml_path, meta_path) = cached_channel_paths(dir, &meta.url);
+    ensure_dir_exists(dir)?;
+    write_file(&toml_path, toml)?;
+    write_file(&meta_path, &serde_json::to_string(meta)?)?;
+    Ok(())
+}
+
+/// Fetches the channel TOML at `url`, keeping a copy of it in the channels directory that is
+/// revalidated with `ETag`/`Last-Modified` on later fetches. Each channel is fetched at most once
+/// per process. In offline mode, only the cached copy is read.
+fn fetch_channel_toml(url: &str) -> Result<String> {
+    if let Some(toml) = fetched_channels().lock().unwrap().get(url) {
+        return Ok(toml.clone());
+    }
+
+    let dir = channels_dir();
+    let cached = read_cached_channel(&dir, url);
+
+    let toml = if is_offline() {
+        match cached {
+            Some((toml, _)) => toml,
+            None => bail!(
+                "No cached copy of channel {} is available in offline mode",
+                url
+            ),
+        }
+    } else {
+        let (etag, last_modified) = cached
+            .as_ref()
+            .map(|(_, meta)| (meta.etag.as_deref(), meta.last_modified.as_deref()))
+            .unwrap_or_default();
+
+        match (download_if_modified(url, etag, last_modified), cached) {
+            (Ok(Conditional::NotModified), Some((toml, _))) => toml,
+            (
+                Ok(Conditional::Modified {
+                    data,
+                    etag,
+                    last_modified,
+                }),
+                _,
+            ) => {
+                let toml = String::from_utf8(data)?;
+                let meta = CachedChannelMeta {
+                    url: url.to_string(),
+                    etag,
+                    last_modified,
+                };
+                // Failing to cache the channel only costs a refetch, so it should not block anything.
+                if let Err(e) = write_cached_channel(&dir, &toml, &meta) {
+                    warn!("Failed to cache channel {}: {}", url, e);
+                }
+                toml
+            }
+            _ => bail!("Could not read {}", url),
+        }
+    };
+
+    fetched_channels()
+        .lock()
+        .unwrap()
+        .insert(url.to_string(), toml.clone());
+
+    Ok(toml)
 }
 
 impl Channel {
     pub fn from_dist_channel(desc: &DistToolchainDescription) -> Result<Self> {
         let channel_url = construct_channel_url(desc)?;
+        let toml = fetch_channel_toml(&channel_url)?;
 
-        let toml = match download(&channel_url) {
-            Ok(t) => String::from_utf8(t)?,
-            Err(_) => bail!("Could not read {}", &channel_url),
-        };
+        let mut channel = Self::from_toml(&toml)?;
+        channel.url = Some(channel_url);
+        Ok(channel)
+    }
+
+    /// Reads a channel from a local file, given either as a path or as a `file://` URL.
+    pub fn from_file(location: &str) -> Result<Self> {
+        let path = Path::new(location.strip_prefix("file://").unwrap_or(location));
+        let toml = read_file("channel", path)
+            .with_context(|| format!("Could not read channel file {}", path.display()))?;
 
-        Self::from_toml(&toml)
+        let mut channel = Self::from_toml(&toml)
+            .with_context(|| format!("Invalid channel file {}", path.display()))?;
+        channel.url = Some(format!("file://{}", path.display()));
+        Ok(channel)
     }
 
     pub fn from_toml(toml: &str) -> Result<Self> {
@@ -97,19 +225,22 @@ impl Channel {
         Ok(channel)
     }
 
-    pub fn build_download_configs(&self) -> Vec<DownloadCfg> {
+    /// Builds the download configs of the channel's published components for `target`.
+    pub fn build_download_configs(&self, target: &TargetTriple) -> Vec<DownloadCfg> {
         let mut cfgs = self
             .pkg
             .iter()
             .filter(|(component_name, _)| Components::contains_published(component_name))
             .map(|(name, package)| {
-                DownloadCfg::from_package(name, package).map_err(|_| {
-                    warn!(
-                        "Failed to recognize component: '{}'.
+                DownloadCfg::from_package(name, package, target)
+                    .map(|cfg| cfg.with_channel(self.url.clone()))
+                    .map_err(|e| {
+                        warn!(
+                            "Failed to recognize component: '{}' ({}).
 If this component should be downloadable, try running `fuelup self update` and re-run the installation.",
-                        &name
-                    )
-                })
+                            &name, e
+                        )
+                    })
             })
             .filter_map(Result::ok)
             .collect::<Vec<DownloadCfg>>();
@@ -121,7 +252,7 @@ If this component should be downloadable, try running `fuelup self update` and r
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{download::DownloadCfg, file::read_file};
+    use crate::download::DownloadCfg;
 
     #[test]
     fn channel_from_toml() {
@@ -215,6 +346,44 @@ mod tests {
         assert!(targets.contains_key("x86_64-unknown-linux-gnu"));
     }
 
+    #[test]
+    fn channel_from_file() {
+        let channel_path = std::env::current_dir()
+            .unwrap()
+            .join("tests/channel-fuel-latest-example.toml");
+
+        for location in [
+            channel_path.display().to_string(),
+            format!("file://{}", channel_path.display()),
+        ] {
+            let channel = Channel::from_file(&location).unwrap();
+            assert_eq!(channel.pkg.keys().len(), 2);
+            assert!(channel.pkg.contains_key("forc"));
+            assert!(channel.pkg.contains_key("fuel-core"));
+        }
+
+        assert!(Channel::from_file("file:///does/not/exist.toml").is_err());
+    }
+
+    #[test]
+    fn cached_channel_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://example.com/channel-fuel-latest.toml";
+        assert!(read_cached_channel(dir.path(), url).is_none());
+
+        let meta = CachedChannelMeta {
+            url: url.to_string(),
+            etag: Some("\"abc123\"".to_string()),
+            last_modified: None,
+        };
+        write_cached_channel(dir.path(), "[pkg]", &meta).unwrap();
+
+        let (toml, cached_meta) = read_cached_channel(dir.path(), url).unwrap();
+        assert_eq!(toml, "[pkg]");
+        assert_eq!(cached_meta, meta);
+        assert!(read_cached_channel(dir.path(), "https://example.com/other.toml").is_none());
+    }
+
     #[test]
     fn download_cfgs_from_channel() {
         let channel_path = std::env::current_dir()
@@ -223,7 +392,8 @@ mod tests {
         let channel_file = read_file("channel-fuel-latest-example", &channel_path).unwrap();
         let channel = Channel::from_toml(&channel_file).unwrap();
 
-        let cfgs: Vec<DownloadCfg> = channel.build_download_configs();
+        let cfgs: Vec<DownloadCfg> =
+            channel.build_download_configs(&TargetTriple::from_host().unwrap());
 
         assert_eq!(cfgs.len(), 2);
         assert_eq!(cfgs[0].name, "forc");
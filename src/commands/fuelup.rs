@@ -1,6 +1,7 @@
 use anyhow::{bail, Result};
 use clap::Parser;
 
+use crate::lock::FuelupLock;
 use crate::ops::fuelup_self::self_update;
 
 #[derive(Debug, Parser)]
@@ -13,6 +14,7 @@ pub enum FuelupCommand {
 struct UpdateCommand {}
 
 pub fn exec() -> Result<()> {
+    let _lock = FuelupLock::acquire()?;
     if let Err(e) = self_update() {
         bail!("fuelup failed to update itself: {}", e)
     };
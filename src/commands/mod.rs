@@ -3,6 +3,10 @@ pub mod completions;
 pub mod component;
 pub mod default;
 pub mod fuelup;
+pub mod overrides;
+pub mod run;
 pub mod show;
+pub mod store;
 pub mod toolchain;
 pub mod update;
+pub mod which;
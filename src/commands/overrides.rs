@@ -0,0 +1,44 @@
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::ops::fuelup_override::{list::list, set::set, unset::unset};
+
+#[derive(Debug, Parser)]
+pub enum OverrideCommand {
+    /// Set the toolchain to use within a directory and its subdirectories
+    Set(SetCommand),
+    /// Remove the override of a directory
+    Unset(UnsetCommand),
+    /// List directory overrides
+    List(ListCommand),
+}
+
+#[derive(Debug, Parser)]
+pub struct SetCommand {
+    /// Toolchain name, which may be a distributable or a custom toolchain
+    pub toolchain: String,
+    /// Directory to set the override for. Defaults to the current directory.
+    #[clap(long)]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct UnsetCommand {
+    /// Directory to remove the override of. Defaults to the current directory.
+    #[clap(long)]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct ListCommand {}
+
+pub fn exec(command: OverrideCommand) -> Result<()> {
+    match command {
+        OverrideCommand::Set(command) => set(command)?,
+        OverrideCommand::Unset(command) => unset(command)?,
+        OverrideCommand::List(command) => list(command)?,
+    };
+
+    Ok(())
+}
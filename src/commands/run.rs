@@ -0,0 +1,23 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::ops::fuelup_run;
+
+#[derive(Debug, Parser)]
+pub struct RunCommand {
+    /// Install the toolchain first if it is not installed yet. Only applies to distributable
+    /// toolchains.
+    #[clap(long)]
+    pub install: bool,
+    /// Toolchain to run the command with, eg. 'nightly' or a custom toolchain
+    pub toolchain: String,
+    /// Component executable to run, eg. 'forc' or 'fuel-core'
+    pub command: String,
+    /// Arguments passed to the command
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub args: Vec<String>,
+}
+
+pub fn exec(command: RunCommand) -> Result<()> {
+    fuelup_run::run(command)
+}
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+use crate::ops::fuelup_store::{gc::gc, list::list, verify::verify};
+
+#[derive(Debug, Parser)]
+pub enum StoreCommand {
+    /// Remove component versions from the store that no toolchain or given project uses
+    Gc(GcCommand),
+    /// List the component versions in the store and the toolchains using them
+    List(ListCommand),
+    /// Check that the component versions in the store are complete and unmodified
+    Verify(VerifyCommand),
+}
+
+#[derive(Debug, Parser)]
+pub struct GcCommand {
+    /// Show what would be removed without removing anything
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Project root whose fuel-toolchain.toml pins component versions to keep. May be repeated.
+    #[clap(long = "project", value_name = "DIR")]
+    pub projects: Vec<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct ListCommand {}
+
+#[derive(Debug, Parser)]
+pub struct VerifyCommand {
+    /// Re-download broken component versions and relink them into the toolchains using them
+    #[clap(long)]
+    pub repair: bool,
+}
+
+pub fn exec(command: StoreCommand) -> Result<()> {
+    match command {
+        StoreCommand::Gc(command) => gc(command)?,
+        StoreCommand::List(command) => list(command)?,
+        StoreCommand::Verify(command) => verify(command)?,
+    };
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anyhow::{bail, Result};
 use clap::Parser;
+use std::path::PathBuf;
 
 use crate::ops::fuelup_toolchain::install::install;
+use crate::ops::fuelup_toolchain::link::link;
 use crate::ops::fuelup_toolchain::list_revisions::list_revisions;
 use crate::ops::fuelup_toolchain::new::new;
 use crate::ops::fuelup_toolchain::uninstall::uninstall;
@@ -14,6 +16,9 @@ pub enum ToolchainCommand {
     Install(InstallCommand),
     /// Create a new custom toolchain
     New(NewCommand),
+    /// Create a custom toolchain that runs the executables in a local directory, such as a build
+    /// output directory
+    Link(LinkCommand),
     /// Uninstall a toolchain
     Uninstall(UninstallCommand),
     /// Fetch the list of published `latest` toolchains, starting from the most recent
@@ -24,6 +29,10 @@ pub enum ToolchainCommand {
 pub struct InstallCommand {
     /// Toolchain name [possible values: latest, beta-1, beta-2, beta-3, beta-4, nightly]
     pub name: String,
+    /// Install a custom toolchain named <NAME> from a local channel TOML file, given as a path or a
+    /// `file://` URL, instead of a published channel.
+    #[clap(long)]
+    pub channel_file: Option<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -33,6 +42,15 @@ pub struct NewCommand {
     pub name: String,
 }
 
+#[derive(Debug, Parser)]
+pub struct LinkCommand {
+    /// Custom toolchain name. Names starting with distributable toolchain names are not allowed.
+    #[clap(value_parser = name_allowed)]
+    pub name: String,
+    /// Directory containing the executables, eg. '~/sway/target/release'
+    pub path: PathBuf,
+}
+
 #[derive(Debug, Parser)]
 pub struct UninstallCommand {
     /// Toolchain to uninstall
@@ -42,7 +60,7 @@ pub struct UninstallCommand {
 #[derive(Debug, Parser)]
 pub struct ListRevisionsCommand {}
 
-fn name_allowed(s: &str) -> Result<String> {
+pub(crate) fn name_allowed(s: &str) -> Result<String> {
     let name = match s.split_once('-') {
         Some((prefix, target_triple)) => {
             if TargetTriple::from_host()?.to_string() == target_triple {
@@ -68,6 +86,7 @@ pub fn exec(command: ToolchainCommand) -> Result<()> {
     match command {
         ToolchainCommand::Install(command) => install(command)?,
         ToolchainCommand::New(command) => new(command)?,
+        ToolchainCommand::Link(command) => link(command)?,
         ToolchainCommand::Uninstall(command) => uninstall(command)?,
         ToolchainCommand::ListRevisions(command) => list_revisions(command)?,
     };
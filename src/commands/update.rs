@@ -4,10 +4,19 @@ use clap::Parser;
 use crate::ops::fuelup_update;
 
 #[derive(Debug, Parser)]
-pub struct UpdateCommand {}
+pub struct UpdateCommand {
+    /// Show which component versions would be updated, added or removed in each toolchain,
+    /// without downloading anything
+    #[clap(long, conflicts_with = "rollback")]
+    pub dry_run: bool,
+    /// Restore the given distributable toolchain to how it was before its last update, instead
+    /// of updating
+    #[clap(long, value_name = "TOOLCHAIN")]
+    pub rollback: Option<String>,
+}
 
-pub fn exec() -> Result<()> {
-    fuelup_update::update()?;
+pub fn exec(command: UpdateCommand) -> Result<()> {
+    fuelup_update::update(command)?;
 
     Ok(())
 }
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::ops::fuelup_which;
+
+#[derive(Debug, Parser)]
+pub struct WhichCommand {
+    /// Command to look up, eg. 'forc' or 'forc-fmt'
+    pub command: String,
+    /// Plugin subcommand, eg. 'fmt' for 'forc fmt'
+    pub subcommand: Option<String>,
+}
+
+pub fn exec(command: WhichCommand) -> Result<()> {
+    fuelup_which::which(command)
+}
@@ -1,11 +1,13 @@
 use time::{format_description::FormatItem, macros::format_description};
 
 pub const FUELUP_GH_PAGES: &str = "https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/";
+pub const GITHUB_RELEASES_URL: &str = "https://github.com/FuelLabs/";
 pub const FUEL_TOOLCHAIN_TOML_FILE: &str = "fuel-toolchain.toml";
 pub const FUELS_VERSION_FILE: &str = "fuels_version";
+pub const CHECKSUMS_FILE: &str = "checksums";
+pub const TOOLCHAIN_MANIFEST_FILE: &str = "manifest.toml";
+pub const TOOLCHAIN_PREVIOUS_DIR: &str = ".previous";
 
-pub const CHANNEL_LATEST_URL: &str =
-    "https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/channel-fuel-beta-4.toml";
 pub const CHANNEL_LATEST_FILE_NAME: &str = "channel-fuel-beta-4.toml";
 pub const CHANNEL_NIGHTLY_FILE_NAME: &str = "channel-fuel-nightly.toml";
 pub const CHANNEL_BETA_1_FILE_NAME: &str = "channel-fuel-beta-1.toml";
@@ -1,27 +1,120 @@
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use component::{Component, FUELUP};
 use flate2::read::GzDecoder;
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
+use std::fs;
 use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
+use std::io::{self, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use std::time::Duration;
-use std::{fs, thread};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use tar::Archive;
+use tracing::info;
 use tracing::warn;
-use tracing::{error, info};
 
 use crate::channel::Channel;
 use crate::channel::Package;
-use crate::constants::CHANNEL_LATEST_URL;
+use crate::constants::{FUELUP_GH_PAGES, GITHUB_RELEASES_URL};
+use crate::fmt::format_bytes;
+use crate::http;
+use crate::path::settings_file;
+use crate::path::{ensure_dir_exists, fuelup_tmp_dir};
+use crate::settings::SettingsFile;
 use crate::target_triple::TargetTriple;
 use crate::toolchain::DistToolchainDescription;
 
+pub const FUELUP_DIST_SERVER: &str = "FUELUP_DIST_SERVER";
+pub const FUELUP_OFFLINE: &str = "FUELUP_OFFLINE";
+pub const FUELUP_CONCURRENT_DOWNLOADS: &str = "FUELUP_CONCURRENT_DOWNLOADS";
+
+const DEFAULT_CONCURRENT_DOWNLOADS: usize = 4;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Puts the current process in offline mode, eg. when `--offline` is passed to fuelup.
+pub fn set_offline() {
+    OFFLINE.store(true, Ordering::Relaxed);
+}
+
+/// Whether fuelup must refrain from accessing the network. This is the case if `--offline` was
+/// passed, or if `FUELUP_OFFLINE` is set to anything other than an empty string, `0` or `false`.
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+        || env::var(FUELUP_OFFLINE)
+            .map(|v| !matches!(v.as_str(), "" | "0" | "false"))
+            .unwrap_or(false)
+}
+
 fn github_releases_download_url(repo: &str, tag: &Version, tarball: &str) -> String {
-    format!("https://github.com/FuelLabs/{repo}/releases/download/v{tag}/{tarball}")
+    dist_url(&format!(
+        "{GITHUB_RELEASES_URL}{repo}/releases/download/v{tag}/{tarball}"
+    ))
+}
+
+/// The base URL of the mirror to fetch channels and release tarballs from, if one was
+/// configured through `FUELUP_DIST_SERVER` or the `dist_server` key in settings.toml.
+pub fn dist_server() -> Option<String> {
+    if let Some(server) = env::var(FUELUP_DIST_SERVER).ok().filter(|s| !s.is_empty()) {
+        return Some(server);
+    }
+
+    let settings_file = settings_file();
+    if settings_file.exists() {
+        return SettingsFile::new(settings_file)
+            .with(|s| Ok(s.dist_server.clone()))
+            .ok()
+            .flatten()
+            .filter(|s| !s.is_empty());
+    }
+
+    None
+}
+
+/// How many components may be downloaded at the same time, as configured through
+/// `FUELUP_CONCURRENT_DOWNLOADS` or the `concurrent_downloads` key in settings.toml.
+pub fn concurrent_downloads() -> usize {
+    if let Some(jobs) = env::var(FUELUP_CONCURRENT_DOWNLOADS)
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .filter(|jobs| *jobs > 0)
+    {
+        return jobs;
+    }
+
+    let settings_file = settings_file();
+    if settings_file.exists() {
+        if let Some(jobs) = SettingsFile::new(settings_file)
+            .with(|s| Ok(s.concurrent_downloads))
+            .ok()
+            .flatten()
+            .filter(|jobs| *jobs > 0)
+        {
+            return jobs;
+        }
+    }
+
+    DEFAULT_CONCURRENT_DOWNLOADS
+}
+
+fn rewrite_dist_url(url: &str, dist_server: &str) -> String {
+    [FUELUP_GH_PAGES, GITHUB_RELEASES_URL]
+        .iter()
+        .find_map(|base| url.strip_prefix(base))
+        .map(|path| format!("{}/{}", dist_server.trim_end_matches('/'), path))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Rewrites a URL pointing to fuelup's gh-pages or to a Fuel Labs GitHub release so that it
+/// points to the configured dist server instead. URLs are returned as-is if no dist server is set.
+pub fn dist_url(url: &str) -> String {
+    match dist_server() {
+        Some(dist_server) => rewrite_dist_url(url, &dist_server),
+        None => url.to_string(),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +132,7 @@ pub struct DownloadCfg {
     tarball_name: String,
     tarball_url: String,
     hash: Option<String>,
+    channel: Option<String>,
 }
 
 impl DownloadCfg {
@@ -69,14 +163,40 @@ impl DownloadCfg {
             tarball_name,
             tarball_url,
             hash: None,
+            channel: None,
         })
     }
 
-    pub fn from_package(name: &str, package: &Package) -> Result<Self> {
-        let target = TargetTriple::from_component(name)?;
+    /// The SHA-256 hash of the tarball, if it is known from the channel the package came from.
+    pub fn hash(&self) -> Option<&str> {
+        self.hash.as_deref()
+    }
+
+    /// The URL of the channel the package came from, if any.
+    pub fn channel(&self) -> Option<&str> {
+        self.channel.as_deref()
+    }
+
+    pub(crate) fn with_channel(mut self, channel: Option<String>) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    pub(crate) fn with_hash(mut self, hash: Option<String>) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    /// Creates the download config of a channel's package for the given toolchain target.
+    pub fn from_package(name: &str, package: &Package, target: &TargetTriple) -> Result<Self> {
+        let target = target.for_component(name)?;
         let tarball_name = tarball_name(name, &package.version, &target);
-        let tarball_url = package.target[&target.to_string()].url.clone();
-        let hash = Some(package.target[&target.to_string()].hash.clone());
+        let binary = package
+            .target
+            .get(&target.to_string())
+            .ok_or_else(|| anyhow!("'{}' is not available for target '{}'", name, target))?;
+        let tarball_url = dist_url(&binary.url);
+        let hash = Some(binary.hash.clone());
         Ok(Self {
             name: name.to_string(),
             target,
@@ -84,20 +204,11 @@ impl DownloadCfg {
             tarball_name,
             tarball_url,
             hash,
+            channel: None,
         })
     }
 }
 
-pub fn build_agent() -> Result<ureq::Agent> {
-    let agent_builder = ureq::builder().user_agent("fuelup");
-
-    if let Ok(proxy) = env::var("http_proxy") {
-        return Ok(agent_builder.proxy(ureq::Proxy::new(proxy)?).build());
-    }
-
-    Ok(agent_builder.build())
-}
-
 pub fn tarball_name(tarball_prefix: &str, version: &Version, target: &TargetTriple) -> String {
     if tarball_prefix == "forc-binaries" {
         format!("{tarball_prefix}-{target}.tar.gz")
@@ -107,14 +218,10 @@ pub fn tarball_name(tarball_prefix: &str, version: &Version, target: &TargetTrip
 }
 
 pub fn get_latest_version(name: &str) -> Result<Version> {
-    let handle = build_agent()?;
-
-    let mut data = Vec::new();
     if name == FUELUP {
         const FUELUP_RELEASES_API_URL: &str =
             "https://api.github.com/repos/FuelLabs/fuelup/releases/latest";
-        let resp = handle.get(FUELUP_RELEASES_API_URL).call()?;
-        resp.into_reader().read_to_end(&mut data)?;
+        let data = http::get_bytes(FUELUP_RELEASES_API_URL)?;
         let response: LatestReleaseApiResponse =
             serde_json::from_str(&String::from_utf8_lossy(&data))?;
 
@@ -122,25 +229,16 @@ pub fn get_latest_version(name: &str) -> Result<Version> {
         let version = Version::parse(version_str)?;
         Ok(version)
     } else {
-        let resp = handle.get(CHANNEL_LATEST_URL).call()?;
-
-        resp.into_reader().read_to_end(&mut data)?;
-
-        if let Ok(channel) =
-            Channel::from_dist_channel(&DistToolchainDescription::from_str("latest")?)
-        {
-            channel
-                .pkg
-                .get(name)
-                .ok_or_else(|| {
-                    anyhow!(
-                        "'{name}' is not a valid, downloadable package in the 'latest' channel."
-                    )
-                })
-                .map(|p| p.version.clone())
-        } else {
-            bail!("Failed to get 'latest' channel")
-        }
+        let channel = Channel::from_dist_channel(&DistToolchainDescription::from_str("latest")?)
+            .map_err(|e| anyhow!("Failed to get 'latest' channel: {}", e))?;
+
+        channel
+            .pkg
+            .get(name)
+            .ok_or_else(|| {
+                anyhow!("'{name}' is not a valid, downloadable package in the 'latest' channel.")
+            })
+            .map(|p| p.version.clone())
     }
 }
 
@@ -149,89 +247,350 @@ fn unpack(tar_path: &Path, dst: &Path) -> Result<()> {
     let decompressed = GzDecoder::new(tar_gz);
     let mut archive = Archive::new(decompressed);
 
-    if let Err(e) = archive.unpack(dst) {
-        error!(
+    let unpacked = archive.unpack(dst).map_err(|e| {
+        anyhow!(
             "{}. The archive could be corrupted or the release may not be ready yet",
             e
-        );
-    };
+        )
+    });
 
     fs::remove_file(tar_path)?;
-    Ok(())
+    unpacked
 }
 
-pub fn download(url: &str) -> Result<Vec<u8>> {
-    const RETRY_ATTEMPTS: u8 = 4;
-    const RETRY_DELAY_SECS: u64 = 3;
+/// The response to a conditional request made through `download_if_modified`.
+pub enum Conditional {
+    NotModified,
+    Modified {
+        data: Vec<u8>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
 
-    let handle = build_agent()?;
+/// Downloads `url` unless it is unchanged since it was last fetched, as identified by the `ETag`
+/// and `Last-Modified` headers that were returned back then.
+pub fn download_if_modified(
+    url: &str,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> Result<Conditional> {
+    let mut headers = vec![];
+    if let Some(etag) = etag {
+        headers.push(("If-None-Match", etag));
+    }
+    if let Some(last_modified) = last_modified {
+        headers.push(("If-Modified-Since", last_modified));
+    }
 
-    for _ in 1..RETRY_ATTEMPTS {
-        match handle.get(url).call() {
-            Ok(response) => {
-                let mut data = Vec::new();
-                response.into_reader().read_to_end(&mut data)?;
+    let response = http::get(url, &headers)?;
+    if response.status() == 304 {
+        return Ok(Conditional::NotModified);
+    }
 
-                return Ok(data);
-            }
-            Err(ureq::Error::Status(404, r)) => {
-                // We've reached download_file stage, which means the tag must be correct.
-                error!("Failed to download from {}", &url);
-                let retry: Option<u64> = r.header("retry-after").and_then(|h| h.parse().ok());
-                let retry = retry.unwrap_or(RETRY_DELAY_SECS);
-                info!("Retrying..");
-                thread::sleep(Duration::from_secs(retry));
-            }
-            Err(e) => {
-                // handle other status code and non-status code errors
-                bail!("Unexpected error: {}", e.to_string());
-            }
+    let etag = response.header("etag").map(String::from);
+    let last_modified = response.header("last-modified").map(String::from);
+    let mut data = Vec::new();
+    response.into_reader().read_to_end(&mut data)?;
+
+    Ok(Conditional::Modified {
+        data,
+        etag,
+        last_modified,
+    })
+}
+
+/// The number of downloads in progress in this process.
+static ACTIVE_DOWNLOADS: AtomicUsize = AtomicUsize::new(0);
+
+/// Reports the progress of a download on stderr with a progress bar, if stderr is a terminal.
+/// Nothing is drawn while other downloads are in progress, as their bars would overwrite it.
+struct Progress {
+    name: String,
+    total: Option<u64>,
+    downloaded: u64,
+    resumed_from: u64,
+    started: Instant,
+    last_drawn: Option<Instant>,
+    enabled: bool,
+}
+
+impl Progress {
+    const BAR_WIDTH: u64 = 30;
+    const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+    fn new(name: &str, total: Option<u64>, resumed_from: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            total,
+            downloaded: resumed_from,
+            resumed_from,
+            started: Instant::now(),
+            last_drawn: None,
+            enabled: io::stderr().is_terminal(),
         }
     }
 
-    bail!("Could not read file");
+    fn draws(&self) -> bool {
+        self.enabled && ACTIVE_DOWNLOADS.load(Ordering::Relaxed) == 1
+    }
+
+    fn inc(&mut self, bytes: u64) {
+        self.downloaded += bytes;
+        if self
+            .last_drawn
+            .is_none_or(|t| t.elapsed() >= Self::REDRAW_INTERVAL)
+        {
+            self.draw();
+        }
+    }
+
+    fn draw(&mut self) {
+        if !self.draws() {
+            return;
+        }
+        self.last_drawn = Some(Instant::now());
+
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let rate = match elapsed > 0.0 {
+            true => (self.downloaded - self.resumed_from) as f64 / elapsed,
+            false => 0.0,
+        };
+
+        let line = match self.total {
+            Some(total) if total > 0 => {
+                let filled = (self.downloaded.min(total) * Self::BAR_WIDTH / total) as usize;
+                let bar = format!(
+                    "{:<width$}",
+                    "=".repeat(filled),
+                    width = Self::BAR_WIDTH as usize
+                );
+                let eta = match rate > 0.0 {
+                    true => format_eta(Duration::from_secs_f64(
+                        total.saturating_sub(self.downloaded) as f64 / rate,
+                    )),
+                    false => "--:--".to_string(),
+                };
+                format!(
+                    "{} [{}] {} / {}  {}/s  ETA {}",
+                    self.name,
+                    bar,
+                    format_bytes(self.downloaded),
+                    format_bytes(total),
+                    format_bytes(rate as u64),
+                    eta
+                )
+            }
+            _ => format!(
+                "{} {}  {}/s",
+                self.name,
+                format_bytes(self.downloaded),
+                format_bytes(rate as u64)
+            ),
+        };
+        // Return to the start of the line and clear what is left of the previous one.
+        eprint!("\r{line}\x1b[K");
+    }
+
+    fn finish(&mut self) {
+        if self.draws() {
+            self.draw();
+            eprintln!();
+        }
+    }
 }
 
-pub fn download_file(url: &str, path: &PathBuf) -> Result<()> {
-    const RETRY_ATTEMPTS: u8 = 4;
-    const RETRY_DELAY_SECS: u64 = 3;
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    match secs / 3600 {
+        0 => format!("{}:{:02}", secs / 60, secs % 60),
+        hours => format!("{}:{:02}:{:02}", hours, (secs % 3600) / 60, secs % 60),
+    }
+}
 
-    let handle = build_agent()?;
+/// Where the partial download of `url` is kept, so that it survives failed installs and can be
+/// resumed by a later download of the same URL.
+fn partial_download_path(url: &str) -> PathBuf {
+    let hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+    let file_name = url.rsplit('/').next().unwrap_or_default();
+    fuelup_tmp_dir()
+        .join("downloads")
+        .join(format!("{}-{}.part", &hash[..16], file_name))
+}
 
-    let mut file = OpenOptions::new().write(true).create(true).open(path)?;
+/// Where the validator of a partial download is kept, which is the `ETag` or `Last-Modified`
+/// header the download started with.
+fn validator_path(part_path: &Path) -> PathBuf {
+    let mut path = part_path.as_os_str().to_owned();
+    path.push(".validator");
+    PathBuf::from(path)
+}
 
-    for _ in 1..RETRY_ATTEMPTS {
-        match handle.get(url).call() {
-            Ok(response) => {
-                let mut data = Vec::new();
-                response.into_reader().read_to_end(&mut data)?;
+/// Returns the validator to send as `If-Range` when resuming a download from `response`. Weak
+/// ETags cannot be used for this, and a download without a validator cannot be resumed safely.
+fn range_validator(response: &ureq::Response) -> Option<String> {
+    response
+        .header("etag")
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| response.header("last-modified"))
+        .map(String::from)
+}
 
-                if let Err(e) = file.write_all(&data) {
-                    error!(
-                        "Something went wrong writing data to {}: {}",
-                        path.display(),
-                        e
-                    )
-                };
+/// Downloads `url` to `path`, streaming the response to disk. The download is kept in a '.part'
+/// file until it completes, and is resumed from where it stopped with an HTTP Range request if
+/// the connection drops, including by a later fuelup process.
+pub fn download_file(url: &str, path: &Path) -> Result<()> {
+    ACTIVE_DOWNLOADS.fetch_add(1, Ordering::Relaxed);
+    let result = resume_download(url, path);
+    ACTIVE_DOWNLOADS.fetch_sub(1, Ordering::Relaxed);
+    result
+}
 
-                return Ok(());
+// Requests are retried by `http::get`, so this only loops to resume a download that was
+// interrupted after making progress, at most as many times as a request is retried.
+fn resume_download(url: &str, path: &Path) -> Result<()> {
+    let part_path = partial_download_path(url);
+    let validator_path = validator_path(&part_path);
+    if let Some(dir) = part_path.parent() {
+        ensure_dir_exists(dir)?;
+    }
+    let name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let max_resumes = http::HttpConfig::get().retries;
+    let mut resumes = 0;
+
+    loop {
+        // A partial download is only resumed if the server can tell whether the file it holds
+        // is still the one that was partially downloaded, which it does through `If-Range`.
+        let validator = fs::read_to_string(&validator_path).ok();
+        let offset = match validator {
+            Some(_) => fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0),
+            None => 0,
+        };
+        let response = match (offset, &validator) {
+            (0, _) | (_, None) => http::get(url, &[])?,
+            (_, Some(validator)) => {
+                let range = format!("bytes={offset}-");
+                match http::get(url, &[("Range", &range), ("If-Range", validator)]) {
+                    Ok(response) => response,
+                    Err(e) => {
+                        warn!("Could not resume download of {}: {}", name, e);
+                        let _ = fs::remove_file(&validator_path);
+                        continue;
+                    }
+                }
             }
-            Err(ureq::Error::Status(404, r)) => {
-                // We've reached download_file stage, which means the tag must be correct.
-                error!("Failed to download from {}", &url);
-                let retry: Option<u64> = r.header("retry-after").and_then(|h| h.parse().ok());
-                let retry = retry.unwrap_or(RETRY_DELAY_SECS);
-                info!("Retrying..");
-                thread::sleep(Duration::from_secs(retry));
+        };
+
+        // The whole file is sent if the server does not support ranges, or if the file changed
+        // since the partial download, in which case the download is started over.
+        let resumed_from = match response.status() {
+            206 => offset,
+            _ => 0,
+        };
+        if resumed_from > 0 {
+            info!(
+                "Resuming download of {} from {}",
+                name,
+                format_bytes(offset)
+            );
+        }
+        let mut file = match resumed_from {
+            0 => {
+                match range_validator(&response) {
+                    Some(validator) => fs::write(&validator_path, validator)?,
+                    None => {
+                        let _ = fs::remove_file(&validator_path);
+                    }
+                }
+                OpenOptions::new()
+                    .write(true)
+                    .create(true)
+                    .truncate(true)
+                    .open(&part_path)?
             }
-            Err(e) => {
-                // handle other status code and non-status code errors
-                bail!("Unexpected error: {}", e.to_string());
+            _ => OpenOptions::new().append(true).open(&part_path)?,
+        };
+
+        let total = response
+            .header("content-length")
+            .and_then(|len| len.parse::<u64>().ok())
+            .map(|len| len + resumed_from);
+        let mut progress = Progress::new(&name, total, resumed_from);
+        let mut reader = response.into_reader();
+        let mut buf = vec![0; 64 * 1024];
+        let interrupted = loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break None,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => break Some(e.to_string()),
+            };
+            file.write_all(&buf[..n]).with_context(|| {
+                format!(
+                    "Something went wrong writing data to {}",
+                    part_path.display()
+                )
+            })?;
+            progress.inc(n as u64);
+        };
+        progress.finish();
+        file.sync_data()?;
+
+        let interrupted = interrupted.or_else(|| {
+            total
+                .filter(|total| progress.downloaded < *total)
+                .map(|total| format!("received {} of {} bytes", progress.downloaded, total))
+        });
+        if let Some(e) = interrupted {
+            // Resuming is only worth it if this attempt got further than the previous one, and is
+            // only possible if the server sent a validator to resume it with.
+            if progress.downloaded <= offset || !validator_path.exists() || resumes >= max_resumes {
+                bail!("Download of {} was interrupted: {}", name, e);
             }
+            warn!("Download of {} was interrupted: {}", name, e);
+            resumes += 1;
+            continue;
         }
+
+        if fs::rename(&part_path, path).is_err() {
+            // The partial download may be on another filesystem than the destination.
+            fs::copy(&part_path, path)?;
+            fs::remove_file(&part_path)?;
+        }
+        let _ = fs::remove_file(&validator_path);
+        return Ok(());
     }
+}
 
-    bail!("Could not download file");
+/// Computes the hex-encoded SHA-256 digest of a file, streaming its contents through the hasher.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Verifies that the file at `path` hashes to `expected_hash`. The file is removed on a mismatch
+/// so that a corrupted or tampered download is never unpacked.
+fn verify_checksum(path: &Path, expected_hash: &str) -> Result<()> {
+    let actual_hash = sha256_file(path)?;
+
+    if !actual_hash.eq_ignore_ascii_case(expected_hash) {
+        let _ = fs::remove_file(path);
+        bail!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            path.file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            expected_hash,
+            actual_hash
+        );
+    }
+
+    Ok(())
 }
 
 pub fn download_file_and_unpack(download_cfg: &DownloadCfg, dst_dir_path: &Path) -> Result<()> {
@@ -253,6 +612,10 @@ pub fn download_file_and_unpack(download_cfg: &DownloadCfg, dst_dir_path: &Path)
         );
     };
 
+    if let Some(hash) = &download_cfg.hash {
+        verify_checksum(&tarball_path, hash)?;
+    }
+
     unpack(&tarball_path, dst_dir_path)?;
 
     Ok(())
@@ -327,17 +690,12 @@ pub fn fetch_fuels_version(cfg: &DownloadCfg) -> Result<String> {
         _ => bail!("invalid component to fetch fuels version for"),
     };
 
-    let handle = if let Ok(proxy) = env::var("http_proxy") {
-        ureq::builder()
-            .user_agent("fuelup")
-            .proxy(ureq::Proxy::new(proxy)?)
-            .build()
-    } else {
-        ureq::builder().user_agent("fuelup").build()
-    };
+    if is_offline() {
+        bail!("Cannot fetch fuels version in offline mode");
+    }
 
-    if let Ok(resp) = handle.get(&url).call() {
-        let cargo_toml = toml_edit::Document::from_str(&resp.into_string()?)?;
+    if let Ok(data) = http::get_bytes(&url) {
+        let cargo_toml = toml_edit::Document::from_str(&String::from_utf8_lossy(&data))?;
         return fuels_version_from_toml(cargo_toml);
     }
 
@@ -386,6 +744,110 @@ fuels = { version = "0.1", features = ["some-feature"] }
         );
     }
 
+    #[test]
+    fn test_rewrite_dist_url() {
+        const MIRROR: &str = "https://artifactory.example.com/fuel/";
+
+        assert_eq!(
+            rewrite_dist_url(
+                "https://raw.githubusercontent.com/FuelLabs/fuelup/gh-pages/channel-fuel-beta-4.toml",
+                MIRROR
+            ),
+            "https://artifactory.example.com/fuel/channel-fuel-beta-4.toml"
+        );
+        assert_eq!(
+            rewrite_dist_url(
+                "https://github.com/FuelLabs/sway/releases/download/v0.46.1/forc-binaries-linux_amd64.tar.gz",
+                MIRROR.trim_end_matches('/')
+            ),
+            "https://artifactory.example.com/fuel/sway/releases/download/v0.46.1/forc-binaries-linux_amd64.tar.gz"
+        );
+        assert_eq!(
+            rewrite_dist_url("https://example.com/forc.tar.gz", MIRROR),
+            "https://example.com/forc.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum() -> Result<()> {
+        with_toolchain_dir(|dir| {
+            let tarball = dir.path().join("forc-binaries-linux_amd64.tar.gz");
+            fs::write(&tarball, b"fuel")?;
+
+            let expected = "acbe4bfc77e55c071db31f2e37c824d75794867d88499107dc8318cb22aceea5";
+            assert_eq!(sha256_file(&tarball)?, expected);
+            assert!(verify_checksum(&tarball, expected).is_ok());
+            assert!(tarball.exists());
+
+            let wrong = "0".repeat(64);
+            let e = verify_checksum(&tarball, &wrong).unwrap_err();
+            assert_eq!(
+                e.to_string(),
+                format!(
+                    "Checksum mismatch for forc-binaries-linux_amd64.tar.gz: expected {wrong}, got {expected}"
+                )
+            );
+            assert!(!tarball.exists());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(Duration::from_secs(0)), "0:00");
+        assert_eq!(format_eta(Duration::from_secs(75)), "1:15");
+        assert_eq!(format_eta(Duration::from_secs(3725)), "1:02:05");
+    }
+
+    #[test]
+    fn test_partial_download_path() {
+        const URL: &str = "https://github.com/FuelLabs/sway/releases/download/v0.46.1/forc-binaries-linux_amd64.tar.gz";
+        let path = partial_download_path(URL);
+        assert_eq!(path.parent().unwrap(), fuelup_tmp_dir().join("downloads"));
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        assert!(file_name.ends_with("-forc-binaries-linux_amd64.tar.gz.part"));
+        assert_eq!(path, partial_download_path(URL));
+        assert_ne!(
+            path,
+            partial_download_path(&URL.replace("v0.46.1", "v0.46.0"))
+        );
+    }
+
+    #[test]
+    fn test_range_validator() {
+        let response = |headers: &str| {
+            format!("HTTP/1.1 200 OK\r\n{headers}\r\n")
+                .parse::<ureq::Response>()
+                .unwrap()
+        };
+        const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+        assert_eq!(
+            range_validator(&response(&format!(
+                "ETag: \"abc\"\r\nLast-Modified: {LAST_MODIFIED}\r\n"
+            ))),
+            Some("\"abc\"".to_string())
+        );
+        assert_eq!(
+            range_validator(&response(&format!(
+                "ETag: W/\"abc\"\r\nLast-Modified: {LAST_MODIFIED}\r\n"
+            ))),
+            Some(LAST_MODIFIED.to_string())
+        );
+        assert_eq!(range_validator(&response("ETag: W/\"abc\"\r\n")), None);
+        assert_eq!(range_validator(&response("")), None);
+
+        let part_path = partial_download_path("https://example.com/forc.tar.gz");
+        assert_eq!(
+            validator_path(&part_path).file_name().unwrap(),
+            format!(
+                "{}.validator",
+                part_path.file_name().unwrap().to_string_lossy()
+            )
+            .as_str()
+        );
+    }
+
     #[test]
     fn test_unpack_and_link_bins() -> Result<()> {
         with_toolchain_dir(|dir| {
@@ -47,6 +47,21 @@ fn symlink_file(_original: &Path, _link: &Path) -> Result<()> {
     bail!("Symbolic link currently only supported on Unix");
 }
 
+/// Returns the total size in bytes of the files within `path`, without following symlinks.
+pub(crate) fn dir_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
 pub fn read_file(name: &'static str, path: &Path) -> Result<String> {
     fs::read_to_string(path).with_context(|| format!("Failed to read {name}"))
 }
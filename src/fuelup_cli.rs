@@ -2,19 +2,31 @@ use anyhow::Result;
 use clap::Parser;
 
 use crate::commands::show::ShowCommand;
-use crate::commands::{check, completions, component, default, fuelup, show, toolchain, update};
+use crate::commands::{
+    check, completions, component, default, fuelup, overrides, run, show, store, toolchain, update,
+    which,
+};
 
 use crate::commands::check::CheckCommand;
 use crate::commands::completions::CompletionsCommand;
 use crate::commands::component::ComponentCommand;
 use crate::commands::default::DefaultCommand;
 use crate::commands::fuelup::FuelupCommand;
+use crate::commands::overrides::OverrideCommand;
+use crate::commands::run::RunCommand;
+use crate::commands::store::StoreCommand;
 use crate::commands::toolchain::ToolchainCommand;
 use crate::commands::update::UpdateCommand;
+use crate::commands::which::WhichCommand;
+use crate::download::set_offline;
 
 #[derive(Debug, Parser)]
 #[clap(name = "fuelup", about = "Fuel Toolchain Manager", version)]
 pub struct Cli {
+    /// Install components from the store and channels from the local cache only, without
+    /// accessing the network. This may also be enabled by setting FUELUP_OFFLINE.
+    #[clap(long, global = true)]
+    offline: bool,
     #[clap(subcommand)]
     command: Commands,
 }
@@ -33,18 +45,32 @@ enum Commands {
     /// Manage your fuelup installation.
     #[clap(name = "self", subcommand)]
     Fuelup(FuelupCommand),
+    /// Set, unset or list the toolchains used within specific directories
+    #[clap(subcommand)]
+    Override(OverrideCommand),
+    /// Run a command from a specific toolchain without changing the default
+    Run(RunCommand),
     /// Install new toolchains or modify/query installed toolchains
     #[clap(subcommand)]
     Toolchain(ToolchainCommand),
     /// Show the active and installed toolchains, as well as the host and fuelup home
     Show(ShowCommand),
+    /// Inspect or clean up the store of installed component versions
+    #[clap(subcommand)]
+    Store(StoreCommand),
     /// Updates the distributable toolchains, if already installed
     Update(UpdateCommand),
+    /// Show the executable a command resolves to and why its toolchain was selected
+    Which(WhichCommand),
 }
 
 pub fn fuelup_cli() -> Result<()> {
     let cli = Cli::parse();
 
+    if cli.offline {
+        set_offline();
+    }
+
     match cli.command {
         Commands::Check(command) => check::exec(command),
         Commands::Completions(command) => completions::exec(command),
@@ -53,8 +79,12 @@ pub fn fuelup_cli() -> Result<()> {
         Commands::Fuelup(command) => match command {
             FuelupCommand::Update => fuelup::exec(),
         },
+        Commands::Override(command) => overrides::exec(command),
+        Commands::Run(command) => run::exec(command),
         Commands::Show(_command) => show::exec(),
+        Commands::Store(command) => store::exec(command),
         Commands::Toolchain(command) => toolchain::exec(command),
-        Commands::Update(_command) => update::exec(),
+        Commands::Update(command) => update::exec(command),
+        Commands::Which(command) => which::exec(command),
     }
 }
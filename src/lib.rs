@@ -6,6 +6,8 @@ pub mod download;
 pub mod file;
 pub mod fmt;
 pub mod fuelup_cli;
+pub mod http;
+pub mod lock;
 pub mod logging;
 pub mod ops;
 pub mod path;
@@ -14,4 +16,5 @@ pub mod settings;
 pub mod store;
 pub mod target_triple;
 pub mod toolchain;
+pub mod toolchain_manifest;
 pub mod toolchain_override;
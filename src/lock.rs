@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::info;
+
+use crate::path::{ensure_dir_exists, fuelup_dir};
+
+pub const LOCK_FILE: &str = "fuelup.lock";
+
+/// Whether this process already holds the lock, so that nested operations don't wait on it.
+static HELD: AtomicBool = AtomicBool::new(false);
+
+/// An advisory lock on the fuelup directory, held by operations that modify toolchains, the store
+/// or settings so that concurrent fuelup processes don't interleave their changes. The lock is
+/// released when this is dropped.
+pub struct FuelupLock {
+    file: Option<File>,
+}
+
+impl FuelupLock {
+    /// Acquires the lock, waiting for other fuelup processes to release it if necessary.
+    pub fn acquire() -> Result<Self> {
+        if HELD.swap(true, Ordering::SeqCst) {
+            return Ok(Self { file: None });
+        }
+
+        match Self::lock_file() {
+            Ok(file) => Ok(Self { file: Some(file) }),
+            Err(e) => {
+                HELD.store(false, Ordering::SeqCst);
+                Err(e)
+            }
+        }
+    }
+
+    fn lock_file() -> Result<File> {
+        let fuelup_dir = fuelup_dir();
+        ensure_dir_exists(&fuelup_dir)?;
+        let lock_path = fuelup_dir.join(LOCK_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open {}", lock_path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                info!("Waiting for another fuelup process to finish...");
+                file.lock()
+                    .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", lock_path.display()))
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+impl Drop for FuelupLock {
+    fn drop(&mut self) {
+        // Closing the file releases the lock.
+        if self.file.take().is_some() {
+            HELD.store(false, Ordering::SeqCst);
+        }
+    }
+}
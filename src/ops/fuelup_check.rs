@@ -11,12 +11,9 @@ use ansiterm::Color;
 use anyhow::Result;
 use component::{self, Components};
 use semver::Version;
+use std::cmp::Ordering::{Equal, Greater, Less};
+use std::collections::HashMap;
 use std::str::FromStr;
-use std::{
-    cmp::Ordering::{Equal, Greater, Less},
-    path::Path,
-};
-use std::{collections::HashMap, process::Command};
 use tracing::{error, info};
 
 fn collect_package_versions(channel: Channel) -> HashMap<String, Version> {
@@ -55,35 +52,19 @@ fn format_version_comparison(current_version: &Version, latest_version: &Version
     }
 }
 
-fn check_plugin(plugin_executable: &Path, plugin: &str, latest_version: &Version) -> Result<()> {
-    match std::process::Command::new(plugin_executable)
-        .arg("--version")
-        .output()
-    {
-        Ok(o) => {
-            let output = String::from_utf8_lossy(&o.stdout).into_owned();
-            match output.split_whitespace().last() {
-                Some(v) => {
-                    let version = Version::parse(v)?;
-                    info!(
-                        "    - {} - {}",
-                        bold(plugin),
-                        format_version_comparison(&version, latest_version)
-                    );
-                }
-                None => {
-                    info!("    - {} - Error getting version string", plugin);
-                }
-            };
-        }
-        Err(e) => {
-            let error_text = if plugin_executable.exists() {
-                format!("execution error - {e}")
-            } else {
-                "not found".into()
-            };
-            info!("    - {} - {}", bold(plugin), error_text);
-        }
+fn check_plugin(
+    toolchain: &Toolchain,
+    executable: &str,
+    plugin: &str,
+    latest_version: &Version,
+) -> Result<()> {
+    match toolchain.executable_version(executable) {
+        Ok(version) => info!(
+            "    - {} - {}",
+            bold(plugin),
+            format_version_comparison(&version, latest_version)
+        ),
+        Err(e) => info!("    - {} - {}", bold(plugin), e),
     }
     Ok(())
 }
@@ -119,25 +100,12 @@ fn check_toolchain(toolchain: &str, verbose: bool) -> Result<()> {
 
     for component in Components::collect_exclude_plugins()? {
         if let Some(latest_version) = latest_package_versions.get(&component.name) {
-            let component_executable = toolchain.bin_path.join(&component.name);
-            match Command::new(component_executable).arg("--version").output() {
-                Ok(o) => {
-                    let output = String::from_utf8_lossy(&o.stdout).into_owned();
-
-                    match output.split_whitespace().last() {
-                        Some(v) => {
-                            let version = Version::parse(v)?;
-                            info!(
-                                "  {} - {}",
-                                bold(&component.name),
-                                format_version_comparison(&version, latest_version)
-                            );
-                        }
-                        None => {
-                            error!("  {} - Error getting version string", bold(&component.name));
-                        }
-                    }
-                }
+            match toolchain.executable_version(&component.name) {
+                Ok(version) => info!(
+                    "  {} - {}",
+                    bold(&component.name),
+                    format_version_comparison(&version, latest_version)
+                ),
                 Err(_) => error!("  {} - Error getting version string", bold(&component.name)),
             };
 
@@ -148,8 +116,6 @@ fn check_toolchain(toolchain: &str, verbose: bool) -> Result<()> {
                     }
 
                     for (index, executable) in plugin.executables.iter().enumerate() {
-                        let plugin_executable = toolchain.bin_path.join(executable);
-
                         let mut plugin_name = &plugin.name;
 
                         if !plugin.is_main_executable() {
@@ -163,7 +129,7 @@ fn check_toolchain(toolchain: &str, verbose: bool) -> Result<()> {
                         );
 
                         if let Some(latest_version) = maybe_latest_version {
-                            check_plugin(&plugin_executable, plugin_name, latest_version)?;
+                            check_plugin(&toolchain, executable, plugin_name, latest_version)?;
                         }
                     }
                 }
@@ -6,8 +6,8 @@ use semver::Version;
 use tracing::info;
 
 use crate::{
-    commands::component::AddCommand, download::DownloadCfg, target_triple::TargetTriple,
-    toolchain::Toolchain,
+    commands::component::AddCommand, download::DownloadCfg, lock::FuelupLock, store::Store,
+    target_triple::TargetTriple, toolchain::Toolchain,
 };
 
 pub fn add(command: AddCommand) -> Result<()> {
@@ -15,7 +15,7 @@ pub fn add(command: AddCommand) -> Result<()> {
         maybe_versioned_component,
     } = command;
 
-    let toolchain = Toolchain::from_settings()?;
+    let toolchain = Toolchain::from_environment_or_settings()?;
     if toolchain.is_distributed() {
         bail!(
             "Installing specific components is reserved for custom toolchains.
@@ -26,6 +26,14 @@ You may create a custom toolchain using 'fuelup toolchain new <toolchain>'.",
         )
     };
 
+    if toolchain.is_linked() {
+        bail!(
+            "Installing components is not supported for '{}', which is linked to a local directory.
+Manage the executables in that directory instead.",
+            toolchain.name
+        )
+    };
+
     let (component, version): (&str, Option<Version>) =
         match maybe_versioned_component.split_once('@') {
             Some(t) => {
@@ -64,6 +72,8 @@ You may create a custom toolchain using 'fuelup toolchain new <toolchain>'.",
 
     let download_cfg =
         DownloadCfg::new(component, TargetTriple::from_component(component)?, version)?;
+    let _lock = FuelupLock::acquire()?;
+    Store::from_env()?.ensure_offline_installable(std::slice::from_ref(&download_cfg))?;
     toolchain.add_component(download_cfg)?;
 
     Ok(())
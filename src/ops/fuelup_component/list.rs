@@ -3,7 +3,6 @@ use crate::{
 };
 use anyhow::Result;
 use component::Components;
-use semver::Version;
 use std::fmt::Write;
 use tracing::info;
 
@@ -34,7 +33,7 @@ fn format_forc_default_plugins(plugin_executables: Vec<String>) -> String {
 }
 
 pub fn list(_command: ListCommand) -> Result<()> {
-    let toolchain = Toolchain::from_settings()?;
+    let toolchain = Toolchain::from_environment_or_settings()?;
     let mut installed_components_summary = String::from("\nInstalled:\n");
     let mut available_components_summary = String::from("Installable:\n");
 
@@ -45,20 +44,10 @@ pub fn list(_command: ListCommand) -> Result<()> {
             |v| v.to_string(),
         );
         if toolchain.has_component(&component.name) {
-            let exec_path = toolchain.bin_path.join(&component.name);
-
-            let current_version = if let Ok(o) = std::process::Command::new(exec_path)
-                .arg("--version")
-                .output()
-            {
-                let output = String::from_utf8_lossy(&o.stdout).into_owned();
-                output.split_whitespace().last().map_or_else(
-                    || None,
-                    |v| Version::parse(v).map_or_else(|_| None, |v| Some(v.to_string())),
-                )
-            } else {
-                None
-            };
+            let current_version = toolchain
+                .executable_version(&component.name)
+                .ok()
+                .map(|v| v.to_string());
 
             let version_info = match Some(&latest_version) == current_version.as_ref() {
                 true => "up-to-date".to_string(),
@@ -1,11 +1,11 @@
 use anyhow::{bail, Result};
 
-use crate::{commands::component::RemoveCommand, toolchain::Toolchain};
+use crate::{commands::component::RemoveCommand, lock::FuelupLock, toolchain::Toolchain};
 
 pub fn remove(command: RemoveCommand) -> Result<()> {
     let RemoveCommand { component } = command;
 
-    let toolchain = Toolchain::from_settings()?;
+    let toolchain = Toolchain::from_environment_or_settings()?;
 
     if toolchain.is_distributed() {
         bail!(
@@ -17,6 +17,15 @@ You may create a custom toolchain using 'fuelup toolchain new <toolchain>'.",
         )
     };
 
+    if toolchain.is_linked() {
+        bail!(
+            "Removing components is not supported for '{}', which is linked to a local directory.
+Manage the executables in that directory instead.",
+            toolchain.name
+        )
+    };
+
+    let _lock = FuelupLock::acquire()?;
     toolchain.remove_component(&component)?;
     Ok(())
 }
@@ -3,10 +3,10 @@ use std::str::FromStr;
 use tracing::info;
 
 use crate::{
+    lock::FuelupLock,
     path::settings_file,
     settings::SettingsFile,
-    toolchain::{DistToolchainDescription, Toolchain},
-    toolchain_override::ToolchainOverride,
+    toolchain::{DistToolchainDescription, Toolchain, ToolchainSource},
 };
 
 pub fn default(toolchain: Option<String>) -> Result<()> {
@@ -16,14 +16,16 @@ pub fn default(toolchain: Option<String>) -> Result<()> {
             let mut result = String::new();
             let current_toolchain = Toolchain::from_settings()?;
 
-            if let Some(to) = ToolchainOverride::from_project_root() {
-                let name =
-                    match DistToolchainDescription::from_str(&to.cfg.toolchain.channel.to_string())
-                    {
-                        Ok(desc) => desc.to_string(),
-                        Err(_) => to.cfg.toolchain.channel.to_string(),
-                    };
-                result.push_str(&format!("{name} (override)"));
+            let (active_toolchain, source) = Toolchain::from_active()?;
+            let label = match source {
+                ToolchainSource::CommandLine => Some("command line"),
+                ToolchainSource::Environment => Some("environment"),
+                ToolchainSource::DirectoryOverride(_) => Some("directory override"),
+                ToolchainSource::ToolchainFile(_) => Some("override"),
+                ToolchainSource::Default => None,
+            };
+            if let Some(label) = label {
+                result.push_str(&format!("{} ({label})", active_toolchain.name));
 
                 if current_toolchain.exists() {
                     result.push_str(", ")
@@ -46,6 +48,7 @@ pub fn default(toolchain: Option<String>) -> Result<()> {
         bail!("Toolchain with name '{}' does not exist", &new_default.name);
     };
 
+    let _lock = FuelupLock::acquire()?;
     let settings = SettingsFile::new(settings_file());
     settings.with_mut(|s| {
         s.default_toolchain = Some(new_default.name.clone());
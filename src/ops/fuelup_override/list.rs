@@ -0,0 +1,23 @@
+use anyhow::Result;
+use tracing::info;
+
+use crate::{commands::overrides::ListCommand, path::settings_file, settings::SettingsFile};
+
+pub fn list(_command: ListCommand) -> Result<()> {
+    let overrides = if settings_file().exists() {
+        SettingsFile::new(settings_file()).with(|s| Ok(s.overrides.clone()))?
+    } else {
+        Default::default()
+    };
+
+    if overrides.is_empty() {
+        info!("no overrides");
+        return Ok(());
+    }
+
+    for (dir, toolchain) in overrides {
+        info!("{}\t{}", dir, toolchain);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use std::{env, path::PathBuf};
+
+pub mod list;
+pub mod set;
+pub mod unset;
+
+/// The directory an override command applies to, as it is keyed in the settings.
+fn override_dir(path: Option<PathBuf>) -> Result<PathBuf> {
+    let dir = match path {
+        Some(path) => env::current_dir()?.join(path),
+        None => env::current_dir()?,
+    };
+    // The directory of an override being unset may no longer exist.
+    Ok(dir.canonicalize().unwrap_or(dir))
+}
@@ -0,0 +1,45 @@
+use anyhow::{bail, Result};
+use std::str::FromStr;
+use tracing::info;
+
+use super::override_dir;
+use crate::{
+    commands::overrides::SetCommand,
+    lock::FuelupLock,
+    path::settings_file,
+    settings::SettingsFile,
+    toolchain::{DistToolchainDescription, Toolchain},
+};
+
+pub fn set(command: SetCommand) -> Result<()> {
+    let SetCommand { toolchain, path } = command;
+
+    let toolchain = match DistToolchainDescription::from_str(&toolchain) {
+        Ok(desc) => Toolchain::from_path(&desc.to_string()),
+        Err(_) => Toolchain::from_path(&toolchain),
+    };
+
+    if !toolchain.exists() {
+        bail!("Toolchain with name '{}' does not exist", &toolchain.name);
+    };
+
+    let dir = override_dir(path)?;
+    if !dir.is_dir() {
+        bail!("Directory '{}' does not exist", dir.display());
+    }
+
+    let _lock = FuelupLock::acquire()?;
+    let settings = SettingsFile::new(settings_file());
+    settings.with_mut(|s| {
+        s.overrides
+            .insert(dir.to_string_lossy().to_string(), toolchain.name.clone());
+        Ok(())
+    })?;
+    info!(
+        "override toolchain for '{}' set to '{}'",
+        dir.display(),
+        toolchain.name
+    );
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use tracing::info;
+
+use super::override_dir;
+use crate::{
+    commands::overrides::UnsetCommand, lock::FuelupLock, path::settings_file,
+    settings::SettingsFile,
+};
+
+pub fn unset(command: UnsetCommand) -> Result<()> {
+    let UnsetCommand { path } = command;
+
+    let dir = override_dir(path)?;
+
+    let _lock = FuelupLock::acquire()?;
+    let settings = SettingsFile::new(settings_file());
+    let removed = settings.with_mut(|s| Ok(s.overrides.remove(dir.to_string_lossy().as_ref())))?;
+
+    match removed {
+        Some(toolchain) => info!(
+            "override toolchain '{}' for '{}' removed",
+            toolchain,
+            dir.display()
+        ),
+        None => info!("no override toolchain set for '{}'", dir.display()),
+    };
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anyhow::{bail, Result};
+use std::ffi::OsString;
+use std::str::FromStr;
+
+use crate::{
+    commands::run::RunCommand,
+    proxy_cli::direct_proxy,
+    toolchain::{DistToolchainDescription, Toolchain, ToolchainSource},
+    toolchain_override::ToolchainOverride,
+};
+
+pub fn run(command: RunCommand) -> Result<()> {
+    let RunCommand {
+        install,
+        toolchain,
+        command,
+        args,
+    } = command;
+
+    let toolchain = Toolchain::from_name(&toolchain);
+    if !toolchain.exists() {
+        if !install {
+            bail!(
+                "toolchain '{}' is not installed; run with '--install' to install it first",
+                toolchain.name
+            );
+        }
+        match DistToolchainDescription::from_str(&toolchain.name) {
+            Ok(description) => toolchain.install_if_nonexistent(&description)?,
+            Err(_) => bail!(
+                "custom toolchain '{}' does not exist and cannot be installed",
+                toolchain.name
+            ),
+        }
+    }
+
+    // Components pinned in the project's fuel-toolchain.toml still apply if it uses this toolchain.
+    let source = match ToolchainOverride::from_project_root() {
+        Some(to)
+            if Toolchain::from_name(&to.cfg.toolchain.channel.to_string()).name
+                == toolchain.name =>
+        {
+            ToolchainSource::ToolchainFile(to)
+        }
+        _ => ToolchainSource::CommandLine,
+    };
+
+    let args: Vec<OsString> = args.into_iter().map(OsString::from).collect();
+    direct_proxy(&command, &args, &toolchain, &source)?;
+    Ok(())
+}
@@ -1,9 +1,7 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
 use component::{self, Components};
 use semver::Version;
 use std::collections::HashMap;
-use std::path::Path;
-use std::str::FromStr;
 use tracing::info;
 
 use crate::fmt::bold;
@@ -13,87 +11,55 @@ use crate::{
     fmt::print_header,
     path::fuelup_dir,
     target_triple::TargetTriple,
-    toolchain::{DistToolchainDescription, Toolchain},
-    toolchain_override::ToolchainOverride,
+    toolchain::{Toolchain, ToolchainSource, FUELUP_TOOLCHAIN},
 };
 
-fn exec_version(component_executable: &Path) -> Result<Version> {
-    match std::process::Command::new(component_executable)
-        .arg("--version")
-        .output()
-    {
-        Ok(o) => {
-            let output = String::from_utf8_lossy(&o.stdout).into_owned();
-            match output.split_whitespace().last() {
-                Some(v) => {
-                    let version = Version::parse(v)?;
-                    Ok(version)
-                }
-                None => {
-                    bail!("Error getting version string");
-                }
-            }
-        }
-        Err(e) => {
-            if component_executable.exists() {
-                bail!("execution error - {}", e);
-            } else {
-                bail!("not found");
-            }
-        }
-    }
-}
-
 pub fn show() -> Result<()> {
     info!("{}: {}", bold("Default host"), TargetTriple::from_host()?);
     info!("{}: {}", bold("fuelup home"), fuelup_dir().display());
 
     print_header("installed toolchains");
     let cfg = Config::from_env()?;
-    let mut active_toolchain = Toolchain::from_settings()?;
-
-    let toolchain_override = ToolchainOverride::from_project_root();
-
-    let override_name = if let Some(toolchain_override) = toolchain_override.as_ref() {
-        match DistToolchainDescription::from_str(
-            &toolchain_override.cfg.toolchain.channel.to_string(),
-        ) {
-            Ok(desc) => Some(desc.to_string()),
-            Err(_) => Some(toolchain_override.cfg.toolchain.channel.to_string()),
-        }
-    } else {
-        None
+    let default_toolchain = Toolchain::from_settings()?;
+    let (active_toolchain, source) = Toolchain::from_active()?;
+
+    let override_label = match source {
+        ToolchainSource::CommandLine => Some(" (command line)"),
+        ToolchainSource::Environment => Some(" (environment)"),
+        ToolchainSource::DirectoryOverride(_) => Some(" (directory override)"),
+        ToolchainSource::ToolchainFile(_) => Some(" (override)"),
+        ToolchainSource::Default => None,
     };
 
     for toolchain in cfg.list_toolchains()? {
         let mut message = toolchain.clone();
-        if toolchain == active_toolchain.name {
+        if toolchain == default_toolchain.name {
             message.push_str(" (default)")
         }
 
-        if Some(toolchain) == override_name {
-            message.push_str(" (override)");
+        if let Some(label) = override_label.filter(|_| toolchain == active_toolchain.name) {
+            message.push_str(label);
         }
         info!("{}", message)
     }
 
-    let mut active_toolchain_message = String::new();
-    if let Some(toolchain_override) = toolchain_override {
-        // We know that the override exists, but we want to show the target triple as well.
-        let override_name = override_name.as_ref().unwrap();
-
-        let should_append_default = &active_toolchain.name == override_name;
-
-        active_toolchain = Toolchain::from_path(override_name);
-        active_toolchain_message.push_str(&format!("{} (override)", active_toolchain.name));
-        if should_append_default {
-            active_toolchain_message.push_str(" (default)");
+    let mut active_toolchain_message = active_toolchain.name.clone();
+    if let Some(label) = override_label {
+        active_toolchain_message.push_str(label);
+    }
+    if active_toolchain.name == default_toolchain.name {
+        active_toolchain_message.push_str(" (default)");
+    }
+    match &source {
+        ToolchainSource::DirectoryOverride(dir) => {
+            active_toolchain_message.push_str(&format!(", path: {}", dir.display()))
         }
-
-        active_toolchain_message
-            .push_str(&format!(", path: {}", toolchain_override.path.display()));
-    } else {
-        active_toolchain_message.push_str(&format!("{} (default)", active_toolchain.name));
+        ToolchainSource::ToolchainFile(toolchain_override) => active_toolchain_message
+            .push_str(&format!(", path: {}", toolchain_override.path.display())),
+        ToolchainSource::Environment => {
+            active_toolchain_message.push_str(&format!(", set by {}", FUELUP_TOOLCHAIN))
+        }
+        ToolchainSource::CommandLine | ToolchainSource::Default => {}
     };
 
     print_header("active toolchain");
@@ -101,8 +67,7 @@ pub fn show() -> Result<()> {
 
     let mut version_map: HashMap<String, Version> = HashMap::new();
     for component in Components::collect_exclude_plugins()? {
-        let component_executable = active_toolchain.bin_path.join(&component.name);
-        let version_text: String = match exec_version(component_executable.as_path()) {
+        let version_text: String = match active_toolchain.executable_version(&component.name) {
             Ok(version) => {
                 version_map.insert(component.name.clone(), version.clone());
                 format!("{}", version)
@@ -118,8 +83,7 @@ pub fn show() -> Result<()> {
                     info!("    - {}", bold(&plugin.name));
 
                     for executable in plugin.executables.iter() {
-                        let plugin_executable = active_toolchain.bin_path.join(executable);
-                        let version_text = match exec_version(plugin_executable.as_path()) {
+                        let version_text = match active_toolchain.executable_version(executable) {
                             Ok(version) => {
                                 version_map.insert(executable.clone(), version.clone());
 
@@ -132,8 +96,7 @@ pub fn show() -> Result<()> {
                         info!("      - {} : {}", bold(executable), version_text);
                     }
                 } else {
-                    let plugin_executable = active_toolchain.bin_path.join(&plugin.name);
-                    let version_text = match exec_version(plugin_executable.as_path()) {
+                    let version_text = match active_toolchain.executable_version(&plugin.name) {
                         Ok(version) => {
                             version_map.insert(plugin.name.clone(), version.clone());
                             format!("{}", version)
@@ -0,0 +1,85 @@
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tracing::info;
+
+use crate::{
+    commands::store::GcCommand,
+    constants::FUEL_TOOLCHAIN_TOML_FILE,
+    file::dir_size,
+    fmt::format_bytes,
+    lock::FuelupLock,
+    path::toolchains_dir,
+    store::{component_dirname, Store},
+    toolchain_override::ToolchainOverride,
+};
+
+pub fn gc(command: GcCommand) -> Result<()> {
+    let GcCommand { dry_run, projects } = command;
+
+    // Staging directories of installs in progress must not be mistaken for interrupted ones.
+    let _lock = FuelupLock::acquire()?;
+    let store = Store::from_env()?;
+    let references = store.referencing_toolchains(&toolchains_dir())?;
+
+    let mut pinned = HashSet::new();
+    for project in projects {
+        let toolchain_file = project.join(FUEL_TOOLCHAIN_TOML_FILE);
+        if !toolchain_file.is_file() {
+            bail!(
+                "No {} found in project root {}",
+                FUEL_TOOLCHAIN_TOML_FILE,
+                project.display()
+            );
+        }
+
+        let toolchain_override = ToolchainOverride::from_path(toolchain_file)?;
+        for (name, version) in toolchain_override.cfg.components.iter().flatten() {
+            pinned.insert(component_dirname(name, version));
+        }
+    }
+
+    let mut unused: Vec<PathBuf> = store
+        .entries()?
+        .into_iter()
+        .filter(|entry| !references.contains_key(entry) && !pinned.contains(entry))
+        .map(|entry| store.path().join(entry))
+        .collect();
+    // Staging directories are only left behind by installs that were interrupted.
+    unused.extend(store.staging_dirs()?);
+
+    if unused.is_empty() {
+        info!(
+            "No unused components found in the store at {}",
+            store.path().display()
+        );
+        return Ok(());
+    }
+
+    let mut reclaimed = 0;
+    for entry_path in unused {
+        let entry = entry_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let size = dir_size(&entry_path)?;
+
+        if dry_run {
+            info!("Would remove {} ({})", entry, format_bytes(size));
+        } else {
+            fs::remove_dir_all(&entry_path)
+                .with_context(|| format!("Failed to remove {}", entry_path.display()))?;
+            info!("Removed {} ({})", entry, format_bytes(size));
+        }
+        reclaimed += size;
+    }
+
+    if dry_run {
+        info!("\n{} would be reclaimed", format_bytes(reclaimed));
+    } else {
+        info!("\nReclaimed {}", format_bytes(reclaimed));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use std::fmt::Write;
+use tracing::info;
+
+use crate::{
+    commands::store::ListCommand,
+    file::dir_size,
+    fmt::{bold, format_bytes},
+    path::toolchains_dir,
+    store::Store,
+};
+
+pub fn list(_command: ListCommand) -> Result<()> {
+    let store = Store::from_env()?;
+    let entries = store.entries()?;
+
+    if entries.is_empty() {
+        info!("The store at {} is empty", store.path().display());
+        return Ok(());
+    }
+
+    let references = store.referencing_toolchains(&toolchains_dir())?;
+    let mut summary = String::new();
+
+    for entry in entries {
+        let size = dir_size(&store.path().join(&entry))
+            .map_or_else(|_| "unknown size".to_string(), format_bytes);
+        writeln!(summary, "{} ({})", bold(&entry), size)?;
+
+        if let Ok(fuels_version) = store.get_entry_fuels_version(&entry) {
+            writeln!(summary, "  fuels version: {}", fuels_version.trim())?;
+        }
+
+        let toolchains = references.get(&entry).map_or_else(
+            || "none".to_string(),
+            |t| t.iter().cloned().collect::<Vec<_>>().join(", "),
+        );
+        writeln!(summary, "  toolchains: {toolchains}")?;
+    }
+
+    info!("{}", summary.trim_end());
+
+    Ok(())
+}
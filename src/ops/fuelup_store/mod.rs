@@ -0,0 +1,3 @@
+pub mod gc;
+pub mod list;
+pub mod verify;
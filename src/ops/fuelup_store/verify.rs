@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use tracing::{error, info};
+
+use crate::{
+    commands::store::VerifyCommand,
+    constants::CHECKSUMS_FILE,
+    download::DownloadCfg,
+    file::{hard_or_symlink_file, is_executable},
+    lock::FuelupLock,
+    path::{toolchain_bin_dir, toolchains_dir},
+    store::{parse_component_dirname, Store},
+    target_triple::TargetTriple,
+    toolchain_manifest::ToolchainManifest,
+};
+
+/// Returns the hash of the tarball a store entry was installed from, as recorded in the manifest
+/// of a toolchain that uses it.
+fn recorded_hash(entry_dir: &Path, toolchains: Option<&BTreeSet<String>>) -> Option<String> {
+    toolchains.into_iter().flatten().find_map(|toolchain| {
+        ToolchainManifest::from_toolchain_dir(&toolchains_dir().join(toolchain))
+            .ok()
+            .flatten()?
+            .components
+            .into_values()
+            .find(|c| c.store_path == entry_dir)?
+            .hash
+    })
+}
+
+/// Re-downloads a broken store entry and relinks it into the toolchains that were using it.
+fn repair_entry(store: &Store, entry: &str, toolchains: Option<&BTreeSet<String>>) -> Result<()> {
+    let (name, version, target) = parse_component_dirname(entry)
+        .ok_or_else(|| anyhow!("could not tell which component this is"))?;
+    let target = match target {
+        Some(target) => target,
+        None => TargetTriple::from_component(name)?,
+    };
+
+    let entry_dir = store.path().join(entry);
+    // The new download has to match what the entry was originally installed from, so that a bad
+    // download fails before the existing entry is replaced.
+    let download_cfg = DownloadCfg::new(name, target, Some(version))?
+        .with_hash(recorded_hash(&entry_dir, toolchains));
+    let checksums = fs::read_to_string(entry_dir.join(CHECKSUMS_FILE)).ok();
+
+    info!("Re-downloading {}", entry);
+    let bins = store.install_component_matching(&download_cfg, checksums.as_deref())?;
+
+    for toolchain in toolchains.into_iter().flatten() {
+        let bin_dir = toolchain_bin_dir(toolchain);
+        for bin in bins.iter().filter(|bin| is_executable(bin)) {
+            if let Some(exe_file_name) = bin.file_name() {
+                hard_or_symlink_file(bin, &bin_dir.join(exe_file_name))?;
+            }
+        }
+        info!("Relinked {} into toolchain '{}'", entry, toolchain);
+    }
+
+    Ok(())
+}
+
+pub fn verify(command: VerifyCommand) -> Result<()> {
+    let VerifyCommand { repair } = command;
+
+    let store = Store::from_env()?;
+    // Hard links into a store entry can only be found while it still exists, so references have
+    // to be collected before anything is repaired.
+    let references = store.referencing_toolchains(&toolchains_dir())?;
+
+    let mut broken = vec![];
+    for entry in store.entries()? {
+        let problems = store.verify_entry(&entry);
+        if problems.is_empty() {
+            info!("{}: ok", entry);
+        } else {
+            info!("{}: broken", entry);
+            for problem in problems {
+                info!("  - {}", problem);
+            }
+            broken.push(entry);
+        }
+    }
+
+    if broken.is_empty() {
+        info!("\nAll components in the store are intact");
+        return Ok(());
+    }
+
+    if !repair {
+        info!(
+            "\nFound {} broken component(s); run 'fuelup store verify --repair' to re-download them",
+            broken.len()
+        );
+        return Ok(());
+    }
+
+    let _lock = FuelupLock::acquire()?;
+    let mut errored = String::new();
+    for entry in broken {
+        if let Err(e) = repair_entry(&store, &entry, references.get(&entry)) {
+            writeln!(errored, "- {entry}: {e}")?;
+        }
+    }
+
+    if errored.is_empty() {
+        info!("\nAll broken components were repaired");
+    } else {
+        error!("\nfuelup failed to repair:\n{}", errored);
+    }
+
+    Ok(())
+}
@@ -1,5 +1,9 @@
+use crate::commands::toolchain::name_allowed;
+use crate::lock::FuelupLock;
 use crate::path::{settings_file, warn_existing_fuel_executables};
 use crate::settings::SettingsFile;
+use crate::store::Store;
+use crate::target_triple::TargetTriple;
 use crate::toolchain::{DistToolchainDescription, Toolchain};
 use crate::{channel::Channel, commands::toolchain::InstallCommand};
 use anyhow::{bail, Result};
@@ -8,15 +12,22 @@ use std::str::FromStr;
 use tracing::{error, info};
 
 pub fn install(command: InstallCommand) -> Result<()> {
-    let InstallCommand { name } = command;
+    let InstallCommand { name, channel_file } = command;
 
-    let description = DistToolchainDescription::from_str(&name)?;
+    // A toolchain installed from a channel file is a custom toolchain, so it may not take the
+    // name of a distributable toolchain.
+    let toolchain_name = match &channel_file {
+        Some(_) => name_allowed(&name)?,
+        None => DistToolchainDescription::from_str(&name)?.to_string(),
+    };
+
+    let _lock = FuelupLock::acquire()?;
 
     let settings_file = settings_file();
     if !settings_file.exists() {
         let settings = SettingsFile::new(settings_file);
         settings.with_mut(|s| {
-            s.default_toolchain = Some(description.to_string());
+            s.default_toolchain = Some(toolchain_name.clone());
             Ok(())
         })?;
     }
@@ -26,13 +37,22 @@ pub fn install(command: InstallCommand) -> Result<()> {
 
     warn_existing_fuel_executables()?;
 
-    let toolchain = Toolchain::from_path(&description.to_string());
-    let cfgs = if let Ok(channel) = Channel::from_dist_channel(&description) {
-        channel.build_download_configs()
-    } else {
-        bail!("Could not build download configs from channel")
+    let toolchain = Toolchain::from_path(&toolchain_name);
+    let cfgs = match channel_file {
+        Some(channel_file) => {
+            Channel::from_file(&channel_file)?.build_download_configs(&TargetTriple::from_host()?)
+        }
+        None => {
+            let description = DistToolchainDescription::from_str(&name)?;
+            match Channel::from_dist_channel(&description) {
+                Ok(channel) => channel.build_download_configs(&description.target()?),
+                Err(e) => bail!("Could not build download configs from channel: {}", e),
+            }
+        }
     };
 
+    Store::from_env()?.ensure_offline_installable(&cfgs)?;
+
     info!(
         "Downloading: {}",
         cfgs.iter()
@@ -40,8 +60,8 @@ pub fn install(command: InstallCommand) -> Result<()> {
             .collect::<String>()
     );
 
-    for cfg in cfgs {
-        match toolchain.add_component(cfg) {
+    for result in toolchain.add_components(cfgs)? {
+        match result {
             Ok(cfg) => writeln!(installed_bins, "- {} {}", cfg.name, cfg.version)?,
             Err(e) => writeln!(errored_bins, "- {e}")?,
         };
@@ -0,0 +1,42 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::os::unix::fs::symlink;
+use tracing::info;
+
+use crate::commands::toolchain::LinkCommand;
+use crate::lock::FuelupLock;
+use crate::path::ensure_dir_exists;
+use crate::toolchain::Toolchain;
+
+pub fn link(command: LinkCommand) -> Result<()> {
+    let LinkCommand { name, path } = command;
+
+    let toolchain = Toolchain::from_path(&name);
+    if toolchain.exists() {
+        bail!("Toolchain with name '{}' already exists", &name)
+    }
+
+    let path = fs::canonicalize(&path)
+        .with_context(|| format!("Could not find directory '{}'", path.display()))?;
+    if !path.is_dir() {
+        bail!("'{}' is not a directory", path.display())
+    }
+
+    let _lock = FuelupLock::acquire()?;
+    ensure_dir_exists(&toolchain.path)?;
+    symlink(&path, &toolchain.bin_path).with_context(|| {
+        format!(
+            "Could not link '{}' to '{}'",
+            toolchain.bin_path.display(),
+            path.display()
+        )
+    })?;
+
+    info!(
+        "Linked toolchain '{name}' to '{}'
+You may use it with 'fuelup default {name}' or '+{name}'",
+        path.display()
+    );
+
+    Ok(())
+}
@@ -1,9 +1,8 @@
 use anyhow::Result;
 use serde::Deserialize;
-use std::io::Read;
 use tracing::info;
 
-use crate::{commands::toolchain::ListRevisionsCommand, download::build_agent};
+use crate::{commands::toolchain::ListRevisionsCommand, http};
 
 #[derive(Debug, Deserialize)]
 struct Content {
@@ -21,15 +20,9 @@ fn strip_channel_name(name: &str) -> String {
 }
 
 pub fn list_revisions(_command: ListRevisionsCommand) -> Result<()> {
-    let handle = build_agent()?;
-
-    let mut data = Vec::new();
-
-    let resp = handle
-        .get("https://api.github.com/repos/fuellabs/fuelup/contents/channels/latest?ref=gh-pages")
-        .call()?;
-
-    resp.into_reader().read_to_end(&mut data)?;
+    let data = http::get_bytes(
+        "https://api.github.com/repos/fuellabs/fuelup/contents/channels/latest?ref=gh-pages",
+    )?;
     let contents: Vec<Content> = serde_json::from_slice(&data)?;
 
     let revisions = contents
@@ -1,4 +1,5 @@
 pub mod install;
+pub mod link;
 pub mod list_revisions;
 pub mod new;
 pub mod uninstall;
@@ -1,4 +1,5 @@
 use crate::commands::toolchain::NewCommand;
+use crate::lock::FuelupLock;
 use crate::path::{ensure_dir_exists, settings_file, toolchain_bin_dir, toolchains_dir};
 use crate::settings::SettingsFile;
 use anyhow::bail;
@@ -10,6 +11,7 @@ use tracing::info;
 pub fn new(command: NewCommand) -> Result<()> {
     let NewCommand { name } = command;
 
+    let _lock = FuelupLock::acquire()?;
     let toolchains_dir = toolchains_dir();
 
     let toolchain_exists = toolchains_dir.is_dir()
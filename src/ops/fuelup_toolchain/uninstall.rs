@@ -5,6 +5,7 @@ use tracing::{error, info};
 use crate::{
     commands::toolchain::UninstallCommand,
     config::Config,
+    lock::FuelupLock,
     ops::fuelup_default,
     toolchain::{DistToolchainDescription, Toolchain},
 };
@@ -24,6 +25,7 @@ pub fn uninstall(command: UninstallCommand) -> Result<()> {
         return Ok(());
     }
 
+    let _lock = FuelupLock::acquire()?;
     match toolchain.uninstall_self() {
         Ok(_) => {
             info!("toolchain '{}' uninstalled", &toolchain.name);
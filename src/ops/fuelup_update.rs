@@ -1,22 +1,39 @@
 use crate::{
     channel::Channel,
+    commands::update::UpdateCommand,
     config::Config,
     fmt::{bold, colored_bold},
+    lock::FuelupLock,
     path::warn_existing_fuel_executables,
-    toolchain::{DistToolchainDescription, Toolchain},
+    store::Store,
+    target_triple::TargetTriple,
+    toolchain::{cache_sway_std_libs, DistToolchainDescription, Toolchain},
 };
 use ansiterm::Color;
 use anyhow::{bail, Result};
+use component::Components;
+use semver::Version;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use tracing::info;
 
 const UPDATED: &str = "updated";
-const PARTIALLY_UPDATED: &str = "partially updated";
+const NOT_UPDATED: &str = "not updated";
 
-pub fn update() -> Result<()> {
+pub fn update(command: UpdateCommand) -> Result<()> {
+    let UpdateCommand { dry_run, rollback } = command;
+    if let Some(toolchain) = rollback {
+        return rollback_toolchain(&toolchain);
+    }
+    if dry_run {
+        return dry_run_update();
+    }
+
+    let _lock = FuelupLock::acquire()?;
     let config = Config::from_env()?;
     let toolchains = config.list_dist_toolchains()?;
     let mut summary: Vec<(String, String)> = Vec::with_capacity(toolchains.len());
+    let store = Store::from_env()?;
 
     warn_existing_fuel_executables()?;
 
@@ -27,11 +44,11 @@ pub fn update() -> Result<()> {
         let description = DistToolchainDescription::from_str(&toolchain)?;
         info!("updating the '{}' toolchain", description);
 
-        let cfgs = if let Ok(channel) = Channel::from_dist_channel(&description) {
-            channel.build_download_configs()
-        } else {
-            bail!("Could not build download configs from channel")
+        let cfgs = match Channel::from_dist_channel(&description) {
+            Ok(channel) => channel.build_download_configs(&description.target()?),
+            Err(e) => bail!("Could not build download configs from channel: {}", e),
         };
+        store.ensure_offline_installable(&cfgs)?;
 
         info!(
             "Downloading: {}",
@@ -39,23 +56,42 @@ pub fn update() -> Result<()> {
                 .map(|c| c.name.clone() + " ")
                 .collect::<String>()
         );
-        for cfg in cfgs {
-            let toolchain = Toolchain::from_path(&description.to_string());
-            match toolchain.add_component(cfg) {
-                Ok(cfg) => installed_bins.push_str(&format!("  - {} {}\n", cfg.name, cfg.version)),
-                Err(e) => errored_bins.push_str(&format!("  - {e}\n")),
+
+        // Every component is downloaded into the store before the toolchain is touched, so that
+        // a failure does not leave it with a mix of old and new components.
+        let mut downloaded_forc = None;
+        for (cfg, result) in cfgs.iter().zip(store.install_components(&cfgs)) {
+            match result {
+                Ok(downloaded) => {
+                    if downloaded.is_some() && cfg.name == component::FORC {
+                        downloaded_forc = Some(cfg);
+                    }
+                    installed_bins.push_str(&format!("  - {} {}\n", cfg.name, cfg.version))
+                }
+                Err(e) => errored_bins.push_str(&format!(
+                    "  - Could not add component {}({}): {e}\n",
+                    cfg.name, cfg.version
+                )),
             };
         }
 
-        let mut status = String::new();
-        if !installed_bins.is_empty() {
-            status = UPDATED.to_string();
+        let status = if errored_bins.is_empty() {
+            let toolchain = Toolchain::from_path(&description.to_string());
+            toolchain.replace_components(&store, &cfgs)?;
+            // Fetch the core and std libs for a new forc, which is only possible if it can run on
+            // the host.
+            if let Some(forc) = downloaded_forc {
+                if TargetTriple::from_component(component::FORC)? == forc.target {
+                    cache_sway_std_libs(toolchain.bin_path.join(component::FORC))?;
+                }
+            }
             installed_bins = format!("  updated components:\n{installed_bins}");
-        }
-
-        if !errored_bins.is_empty() {
-            status = PARTIALLY_UPDATED.to_string();
-            errored_bins = format!("  failed to update:\n{errored_bins}");
+            UPDATED
+        } else {
+            installed_bins.clear();
+            errored_bins =
+                format!("  failed to update, the toolchain was left unchanged:\n{errored_bins}");
+            NOT_UPDATED
         };
 
         summary.push((
@@ -66,11 +102,7 @@ pub fn update() -> Result<()> {
 
     info!("");
     for (toolchain_info, components_info) in summary {
-        if !toolchain_info
-            .matches(UPDATED)
-            .collect::<String>()
-            .is_empty()
-        {
+        if toolchain_info.ends_with(&format!(" {UPDATED}")) {
             info!("{}", colored_bold(Color::Green, &toolchain_info));
         } else {
             info!("{}", bold(&toolchain_info));
@@ -80,3 +112,126 @@ pub fn update() -> Result<()> {
 
     Ok(())
 }
+
+fn rollback_toolchain(name: &str) -> Result<()> {
+    let description = DistToolchainDescription::from_str(name)?;
+    let toolchain = Toolchain::from_path(&description.to_string());
+    if !toolchain.exists() {
+        bail!("toolchain '{}' is not installed", toolchain.name);
+    }
+
+    let _lock = FuelupLock::acquire()?;
+    toolchain.rollback()?;
+    info!(
+        "toolchain '{}' rolled back to its installation before the last update",
+        toolchain.name
+    );
+    Ok(())
+}
+
+/// Returns the versions of the published components installed in `toolchain`. A version is None
+/// if the component is installed but its version could not be determined.
+fn installed_versions(toolchain: &Toolchain) -> Result<BTreeMap<String, Option<Version>>> {
+    let mut installed = BTreeMap::new();
+    for component in Components::collect_publishables()? {
+        if toolchain.has_component(&component.name) {
+            let version = toolchain.executable_version(&component.name).ok();
+            installed.insert(component.name, version);
+        }
+    }
+    Ok(installed)
+}
+
+/// Prints what `fuelup update` would change in each distributable toolchain, without downloading
+/// any components.
+fn dry_run_update() -> Result<()> {
+    let config = Config::from_env()?;
+
+    for toolchain in config.list_dist_toolchains()? {
+        let description = DistToolchainDescription::from_str(&toolchain)?;
+        let channel = match Channel::from_dist_channel(&description) {
+            Ok(channel) => channel,
+            Err(e) => bail!("Could not build download configs from channel: {}", e),
+        };
+        let installed = installed_versions(&Toolchain::from_path(&description.to_string()))?;
+        let changes = channel_changes(&channel, &description.target()?, installed);
+
+        info!("{}", bold(&description.to_string()));
+        info!("{}", changes.trim_end());
+    }
+
+    Ok(())
+}
+
+/// Describes, one component per line, how updating the components in `installed` to those
+/// published for `target` in `channel` would change them.
+fn channel_changes(
+    channel: &Channel,
+    target: &TargetTriple,
+    mut installed: BTreeMap<String, Option<Version>>,
+) -> String {
+    let mut changes = String::new();
+    for cfg in channel.build_download_configs(target) {
+        match installed.remove(&cfg.name) {
+            Some(Some(version)) if version == cfg.version => {
+                changes.push_str(&format!("  {}: {} (up to date)\n", cfg.name, version))
+            }
+            Some(Some(version)) => {
+                changes.push_str(&format!("  {}: {} -> {}\n", cfg.name, version, cfg.version))
+            }
+            Some(None) => changes.push_str(&format!(
+                "  {}: unknown version -> {}\n",
+                cfg.name, cfg.version
+            )),
+            None => changes.push_str(&format!("  {}: added {}\n", cfg.name, cfg.version)),
+        }
+    }
+    for (name, version) in installed {
+        match version {
+            Some(version) => changes.push_str(&format!("  {name}: removed ({version})\n")),
+            None => changes.push_str(&format!("  {name}: removed\n")),
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(v: &str) -> Option<Version> {
+        Some(Version::parse(v).unwrap())
+    }
+
+    #[test]
+    fn channel_changes_against_installed() {
+        let channel_path = std::env::current_dir()
+            .unwrap()
+            .join("tests/channel-fuel-latest-example.toml");
+        let channel = Channel::from_file(&channel_path.display().to_string()).unwrap();
+        let target = TargetTriple::new("x86_64-unknown-linux-gnu").unwrap();
+
+        let installed = BTreeMap::from([
+            ("forc".to_string(), version("0.1.0")),
+            ("fuel-core".to_string(), version("0.9.4")),
+            ("forc-wallet".to_string(), version("0.1.0")),
+            ("fuel-indexer".to_string(), None),
+        ]);
+        assert_eq!(
+            channel_changes(&channel, &target, installed),
+            "  forc: 0.1.0 -> 0.17.0
+  fuel-core: 0.9.4 (up to date)
+  forc-wallet: removed (0.1.0)
+  fuel-indexer: removed
+"
+        );
+
+        let installed = BTreeMap::from([("forc".to_string(), None)]);
+        assert_eq!(
+            channel_changes(&channel, &target, installed),
+            "  forc: unknown version -> 0.17.0
+  fuel-core: added 0.9.4
+"
+        );
+    }
+}
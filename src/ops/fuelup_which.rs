@@ -0,0 +1,44 @@
+use anyhow::{bail, Result};
+use std::ffi::OsString;
+use tracing::info;
+
+use crate::{
+    commands::which::WhichCommand,
+    proxy_cli::{plugin_name, resolve_bin_path},
+    toolchain::{Toolchain, ToolchainSource, FUELUP_TOOLCHAIN},
+};
+
+pub fn which(command: WhichCommand) -> Result<()> {
+    let WhichCommand {
+        command,
+        subcommand,
+    } = command;
+
+    let (toolchain, source) = Toolchain::from_active()?;
+
+    let args: Vec<OsString> = subcommand.into_iter().map(OsString::from).collect();
+    let proc_name = plugin_name(&command, &args)?.unwrap_or(command);
+    let bin_path = resolve_bin_path(&proc_name, &toolchain, &source)?;
+    if !bin_path.exists() {
+        bail!(
+            "component '{}' not found in currently active toolchain '{}'",
+            proc_name,
+            toolchain.name
+        );
+    }
+
+    let reason = match &source {
+        ToolchainSource::CommandLine => String::from("command line"),
+        ToolchainSource::Environment => format!("{} environment variable", FUELUP_TOOLCHAIN),
+        ToolchainSource::DirectoryOverride(dir) => {
+            format!("directory override for {}", dir.display())
+        }
+        ToolchainSource::ToolchainFile(to) => format!("override file {}", to.path.display()),
+        ToolchainSource::Default => String::from("default toolchain"),
+    };
+
+    info!("{}", bin_path.display());
+    info!("  toolchain: {}", toolchain.name);
+    info!("  selected by: {}", reason);
+    Ok(())
+}
@@ -2,7 +2,11 @@ pub mod fuelup_check;
 pub mod fuelup_completions;
 pub mod fuelup_component;
 pub mod fuelup_default;
+pub mod fuelup_override;
+pub mod fuelup_run;
 pub mod fuelup_self;
 pub mod fuelup_show;
+pub mod fuelup_store;
 pub mod fuelup_toolchain;
 pub mod fuelup_update;
+pub mod fuelup_which;
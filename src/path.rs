@@ -14,8 +14,19 @@ use crate::constants::FUEL_TOOLCHAIN_TOML_FILE;
 pub const FUELUP_DIR: &str = ".fuelup";
 pub const FUELUP_HOME: &str = "FUELUP_HOME";
 
+/// The root of the fuelup installation. This is `$FUELUP_HOME` if set, and `~/.fuelup` otherwise.
 pub fn fuelup_dir() -> PathBuf {
-    dirs::home_dir().unwrap().join(FUELUP_DIR)
+    match env::var_os(FUELUP_HOME).filter(|home| !home.is_empty()) {
+        Some(home) => {
+            let home = PathBuf::from(home);
+            if home.is_absolute() {
+                home
+            } else {
+                env::current_dir().map(|d| d.join(&home)).unwrap_or(home)
+            }
+        }
+        None => dirs::home_dir().unwrap().join(FUELUP_DIR),
+    }
 }
 
 pub fn fuelup_bin_dir() -> PathBuf {
@@ -46,6 +57,10 @@ pub fn store_dir() -> PathBuf {
     fuelup_dir().join("store")
 }
 
+pub fn channels_dir() -> PathBuf {
+    fuelup_dir().join("channels")
+}
+
 pub fn fuelup_tmp_dir() -> PathBuf {
     fuelup_dir().join("tmp")
 }
@@ -85,10 +100,11 @@ pub fn warn_existing_fuel_executables() -> Result<()> {
                 .map(|d| d.join(e.clone()))
                 .find(|f| is_executable(f))
             {
+                let fuelup_bin_dir = fuelup_bin_dir();
+                let is_fuelup_bin = path.parent() == Some(fuelup_bin_dir.as_path());
                 let path = path.to_str().unwrap_or_default();
                 let mut message = String::new();
-                let fuelup_bin_dir = fuelup_bin_dir();
-                if !path.contains(fuelup_bin_dir.to_str().unwrap()) {
+                if !is_fuelup_bin {
                     let maybe_fuelup_executable = fuelup_bin_dir.join(&e);
 
                     message.push_str(&format!("warning: '{e}' found in PATH at {path}."));
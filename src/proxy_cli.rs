@@ -1,92 +1,144 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::ffi::OsString;
 use std::io::{Error, ErrorKind};
 use std::os::unix::prelude::CommandExt;
+use std::path::PathBuf;
 use std::process::{Command, ExitCode, Stdio};
 use std::str::FromStr;
 use std::{env, io};
 
 use crate::download::DownloadCfg;
+use crate::lock::FuelupLock;
 use crate::store::Store;
 use crate::target_triple::TargetTriple;
-use crate::toolchain::{DistToolchainDescription, Toolchain};
-use crate::toolchain_override::ToolchainOverride;
+use crate::toolchain::{DistToolchainDescription, Toolchain, ToolchainSource, FUELUP_TOOLCHAIN};
 use component::Components;
 
 /// Runs forc or fuel-core in proxy mode
 pub fn proxy_run(arg0: &str) -> Result<ExitCode> {
-    let cmd_args: Vec<_> = env::args_os().skip(1).collect();
-    let toolchain = Toolchain::from_settings()?;
+    let mut cmd_args: Vec<_> = env::args_os().skip(1).collect();
 
-    if !cmd_args.is_empty() {
-        let plugin = format!("{}-{}", arg0, &cmd_args[0].to_string_lossy());
-        if Components::collect_plugin_executables()?.contains(&plugin) {
-            direct_proxy(&plugin, &cmd_args[1..], &toolchain)?;
+    // A leading '+<toolchain>' argument selects the toolchain for this invocation only.
+    let plus_toolchain = cmd_args
+        .first()
+        .and_then(|arg| arg.to_str())
+        .and_then(|arg| arg.strip_prefix('+'))
+        .map(Toolchain::from_name);
+    let (toolchain, source) = match plus_toolchain {
+        Some(toolchain) => {
+            if !toolchain.exists() {
+                bail!(
+                    "toolchain '{}' is not installed; you may install it with 'fuelup toolchain install'",
+                    toolchain.name
+                );
+            }
+            cmd_args.remove(0);
+            (toolchain, ToolchainSource::CommandLine)
         }
+        None => Toolchain::from_active()?,
+    };
+
+    if let Some(plugin) = plugin_name(arg0, &cmd_args)? {
+        direct_proxy(&plugin, &cmd_args[1..], &toolchain, &source)?;
     }
 
-    direct_proxy(arg0, &cmd_args, &toolchain)?;
+    direct_proxy(arg0, &cmd_args, &toolchain, &source)?;
     Ok(ExitCode::SUCCESS)
 }
 
-fn direct_proxy(proc_name: &str, args: &[OsString], toolchain: &Toolchain) -> Result<ExitCode> {
-    let toolchain_override: Option<ToolchainOverride> = ToolchainOverride::from_project_root();
-
-    let (bin_path, toolchain_name) = match toolchain_override {
-        Some(to) => {
-            // unwrap() is safe here since we try DistToolchainDescription::from_str()
-            // when deserializing from the toml.
-            let description =
-                DistToolchainDescription::from_str(&to.cfg.toolchain.channel.to_string()).unwrap();
-            let toolchain = Toolchain::from_path(&description.to_string());
-
-            // Install the entire toolchain declared in [toolchain] if it does not exist.
-            toolchain.install_if_nonexistent(&description)?;
-
-            // Plugins distributed by forc have to be handled a little differently,
-            // if one of them is called we want to check for 'forc' instead.
-            let component_name = if Components::is_distributed_by_forc(proc_name) {
-                component::FORC
-            } else {
-                proc_name
-            };
+/// Returns the name of the plugin executable if `args` start with a plugin subcommand of `arg0`,
+/// eg. 'forc-fmt' for `forc fmt`.
+pub(crate) fn plugin_name(arg0: &str, args: &[OsString]) -> Result<Option<String>> {
+    match args.first() {
+        Some(subcommand) => {
+            let plugin = format!("{}-{}", arg0, subcommand.to_string_lossy());
+            Ok(Components::collect_plugin_executables()?
+                .contains(&plugin)
+                .then_some(plugin))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Plugins distributed by forc have to be handled a little differently,
+/// if one of them is called we want to check for 'forc' instead.
+fn component_name(proc_name: &str) -> &str {
+    if Components::is_distributed_by_forc(proc_name) {
+        component::FORC
+    } else {
+        proc_name
+    }
+}
+
+/// Returns the path of the executable `proc_name` runs when `toolchain` was selected from `source`.
+pub(crate) fn resolve_bin_path(
+    proc_name: &str,
+    toolchain: &Toolchain,
+    source: &ToolchainSource,
+) -> Result<PathBuf> {
+    if let ToolchainSource::ToolchainFile(to) = source {
+        // If a specific version is declared, we want to call it from the store and not from the toolchain directory.
+        let component_name = component_name(proc_name);
+        if let Some(version) = to.get_component_version(component_name) {
+            return Ok(Store::from_env()?
+                .component_dir_path(component_name, version)
+                .join(proc_name));
+        }
+    }
+    Ok(toolchain.bin_path.join(proc_name))
+}
 
-            // If a specific version is declared, we want to call it from the store and not from the toolchain directory.
-            if let Some(version) = to.get_component_version(component_name) {
-                let store = Store::from_env()?;
+pub(crate) fn direct_proxy(
+    proc_name: &str,
+    args: &[OsString],
+    toolchain: &Toolchain,
+    source: &ToolchainSource,
+) -> Result<ExitCode> {
+    if let ToolchainSource::ToolchainFile(to) = source {
+        // unwrap() is safe here since we try DistToolchainDescription::from_str()
+        // when deserializing from the toml.
+        let description =
+            DistToolchainDescription::from_str(&to.cfg.toolchain.channel.to_string()).unwrap();
 
+        // Install the entire toolchain declared in [toolchain] if it does not exist.
+        toolchain.install_if_nonexistent(&description)?;
+
+        let component_name = component_name(proc_name);
+        if let Some(version) = to.get_component_version(component_name) {
+            let store = Store::from_env()?;
+
+            if !store.has_component(component_name, version) {
+                // Another process may have installed it while we were waiting for the lock.
+                let _lock = FuelupLock::acquire()?;
                 if !store.has_component(component_name, version) {
                     let download_cfg = DownloadCfg::new(
                         component_name,
                         TargetTriple::from_component(component_name)?,
                         Some(version.clone()),
                     )?;
+                    store.ensure_offline_installable(std::slice::from_ref(&download_cfg))?;
                     // Install components within [components] that are declared but missing from the store.
                     store.install_component(&download_cfg)?;
-                };
-
-                (
-                    store
-                        .component_dir_path(component_name, version)
-                        .join(proc_name),
-                    description.to_string(),
-                )
-            } else {
-                (toolchain.bin_path.join(proc_name), description.to_string())
-            }
+                }
+            };
         }
-        None => (
-            toolchain.bin_path.join(proc_name),
-            toolchain.name.to_owned(),
-        ),
-    };
+    }
+    let bin_path = resolve_bin_path(proc_name, toolchain, source)?;
+    let toolchain_name = &toolchain.name;
 
     let mut cmd = Command::new(bin_path);
 
     cmd.args(args);
     cmd.stdin(Stdio::inherit());
 
-    return exec(&mut cmd, proc_name, &toolchain_name).map_err(anyhow::Error::from);
+    // Keep plugins and other components invoked by this one on the same toolchain. A
+    // fuel-toolchain.toml may pin component versions outside of the toolchain, so in that case
+    // the child is left to resolve the same file itself.
+    if !matches!(source, ToolchainSource::ToolchainFile(_)) {
+        cmd.env(FUELUP_TOOLCHAIN, toolchain_name);
+    }
+
+    return exec(&mut cmd, proc_name, toolchain_name).map_err(anyhow::Error::from);
 
     fn exec(cmd: &mut Command, proc_name: &str, toolchain_name: &str) -> io::Result<ExitCode> {
         let error = cmd.exec();
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
-use std::{cell::RefCell, path::PathBuf};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 use toml_edit::{de, ser, Document};
 
 use anyhow::Result;
@@ -70,6 +74,25 @@ impl SettingsFile {
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Settings {
     pub default_toolchain: Option<String>,
+    /// Base URL of a mirror serving channels and release tarballs.
+    pub dist_server: Option<String>,
+    /// How many components may be downloaded at the same time.
+    pub concurrent_downloads: Option<usize>,
+    /// How many times HTTP requests are retried after a transient failure.
+    pub http_retries: Option<u32>,
+    /// Timeout for connecting to a server, in seconds.
+    pub http_connect_timeout: Option<u64>,
+    /// Timeout for reading from a server, in seconds.
+    pub http_timeout: Option<u64>,
+    /// Proxy for HTTP requests, used unless one is set through the environment.
+    pub proxy: Option<String>,
+    /// PEM file of CA certificates to trust, eg. those of a TLS-inspecting proxy.
+    pub ca_bundle: Option<PathBuf>,
+    /// Whether to trust the CA certificates of the platform's certificate store.
+    pub native_certs: Option<bool>,
+    /// Toolchains set with `fuelup override set`, keyed by the directory they apply to.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub overrides: BTreeMap<String, String>,
 }
 
 impl Settings {
@@ -78,6 +101,16 @@ impl Settings {
         Ok(settings)
     }
 
+    /// Finds the directory override that applies to `dir`, which is the one set for `dir` or for
+    /// its closest ancestor. Returns the overridden directory and its toolchain.
+    pub fn find_override(&self, dir: &Path) -> Option<(PathBuf, String)> {
+        dir.ancestors().find_map(|ancestor| {
+            self.overrides
+                .get(ancestor.to_string_lossy().as_ref())
+                .map(|toolchain| (ancestor.to_path_buf(), toolchain.clone()))
+        })
+    }
+
     pub(crate) fn to_string(&self) -> Result<String> {
         Ok(self.to_toml()?.to_string())
     }
@@ -143,8 +176,44 @@ mod tests {
 
         let settings = Settings {
             default_toolchain: Some("yet-another-default-toolchain".to_string()),
+            ..Default::default()
         };
 
         assert_eq!(settings.to_string().unwrap(), expected_toml);
     }
+
+    #[test]
+    fn overrides_round_trip() {
+        let mut settings = Settings::default();
+        settings
+            .overrides
+            .insert("/home/user/project".to_string(), "my-toolchain".to_string());
+
+        let settings = Settings::parse(&settings.to_string().unwrap()).unwrap();
+        assert_eq!(settings.overrides["/home/user/project"], "my-toolchain");
+    }
+
+    #[test]
+    fn find_override() {
+        let mut settings = Settings::default();
+        settings
+            .overrides
+            .insert("/home/user".to_string(), "latest".to_string());
+        settings
+            .overrides
+            .insert("/home/user/project".to_string(), "my-toolchain".to_string());
+
+        assert_eq!(
+            settings.find_override(Path::new("/home/user/project/src")),
+            Some((
+                PathBuf::from("/home/user/project"),
+                "my-toolchain".to_string()
+            ))
+        );
+        assert_eq!(
+            settings.find_override(Path::new("/home/user/other")),
+            Some((PathBuf::from("/home/user"), "latest".to_string()))
+        );
+        assert_eq!(settings.find_override(Path::new("/home")), None);
+    }
 }
@@ -1,22 +1,143 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
-use std::io::Write;
+use std::io::{self, Write};
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use component::Component;
 use semver::Version;
 use tracing::{info, warn};
 
 use crate::{
-    constants::FUELS_VERSION_FILE,
-    download::{download_file_and_unpack, fetch_fuels_version, unpack_bins, DownloadCfg},
+    constants::{CHECKSUMS_FILE, FUELS_VERSION_FILE, TOOLCHAIN_PREVIOUS_DIR},
+    download::{
+        concurrent_downloads, download_file_and_unpack, fetch_fuels_version, is_offline,
+        sha256_file, unpack_bins, DownloadCfg,
+    },
+    file::{is_executable, write_file},
     path::{ensure_dir_exists, store_dir},
+    target_triple::TargetTriple,
 };
 
-fn component_dirname(component_name: &str, version: &Version) -> String {
+/// Prefix of the hidden directories in the store that components are staged in while installing.
+const STAGING_DIR_PREFIX: &str = ".tmp-";
+
+pub(crate) fn component_dirname(component_name: &str, version: &Version) -> String {
     format!("{component_name}-{version}")
 }
 
+/// The name of the store entry that `cfg` is installed in. Components for a target other than the
+/// host are suffixed with that target, eg. 'forc-0.17.0-linux_arm64'.
+fn cfg_dirname(cfg: &DownloadCfg) -> String {
+    let dirname = component_dirname(&cfg.name, &cfg.version);
+    match TargetTriple::from_component(&cfg.name) {
+        Ok(host) if host == cfg.target => dirname,
+        _ => format!("{dirname}-{}", cfg.target),
+    }
+}
+
+/// Splits a store entry named '<component_name>-<version>[-<target>]' into its component name,
+/// version and target, which is None for the host.
+pub(crate) fn parse_component_dirname(
+    dirname: &str,
+) -> Option<(&str, Version, Option<TargetTriple>)> {
+    dirname.match_indices('-').find_map(|(i, _)| {
+        let (name, rest) = (&dirname[..i], &dirname[i + 1..]);
+
+        // A target suffix has to be looked for first, since it would otherwise be taken for the
+        // pre-release part of the version.
+        let targeted = rest.match_indices('-').find_map(|(j, _)| {
+            let target = TargetTriple::from_component_target(name, &rest[j + 1..]).ok()?;
+            Some((name, Version::parse(&rest[..j]).ok()?, Some(target)))
+        });
+
+        targeted.or_else(|| Some((name, Version::parse(rest).ok()?, None)))
+    })
+}
+
+/// Calls `f` on every item with up to `jobs` threads, returning the results in the order of
+/// `items` regardless of the order they complete in.
+fn map_concurrently<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<Option<R>>>());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else { break };
+                let result = f(item);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("every item is mapped"))
+        .collect()
+}
+
+/// Writes the SHA-256 checksums of the files named like `bins` within `component_dir` into its
+/// `CHECKSUMS_FILE`, in the format used by `sha256sum`.
+fn write_checksums(component_dir: &Path, bins: &[PathBuf]) -> Result<()> {
+    let mut checksums = String::new();
+    for file_name in bins.iter().filter_map(|bin| bin.file_name()) {
+        checksums.push_str(&format!(
+            "{}  {}\n",
+            sha256_file(&component_dir.join(file_name))?,
+            file_name.to_string_lossy()
+        ));
+    }
+    write_file(&component_dir.join(CHECKSUMS_FILE), &checksums)?;
+    Ok(())
+}
+
+/// Checks the files of a store entry named `entry` within `entry_dir`, which may be where it is
+/// staged rather than where it is in the store. See `Store::verify_entry`.
+fn verify_component_dir(entry: &str, entry_dir: &Path) -> Vec<String> {
+    let mut problems = vec![];
+
+    match parse_component_dirname(entry).map(|(name, _, _)| Component::from_name(name)) {
+        Some(Ok(component)) => {
+            for exe in &component.executables {
+                let exe_path = entry_dir.join(exe);
+                if !exe_path.exists() {
+                    problems.push(format!("missing executable '{exe}'"));
+                } else if !is_executable(&exe_path) {
+                    problems.push(format!("'{exe}' is not executable"));
+                }
+            }
+        }
+        Some(Err(e)) => problems.push(e.to_string()),
+        None => problems.push("not named '<component>-<version>'".to_string()),
+    }
+
+    if let Ok(checksums) = fs::read_to_string(entry_dir.join(CHECKSUMS_FILE)) {
+        for (expected, file) in checksums.lines().filter_map(|l| l.split_once("  ")) {
+            let file_path = entry_dir.join(file);
+            if !file_path.exists() {
+                continue;
+            }
+            match sha256_file(&file_path) {
+                Ok(actual) if actual == expected => {}
+                Ok(_) => problems.push(format!("checksum mismatch for '{file}'")),
+                Err(e) => problems.push(format!("could not read '{file}': {e}")),
+            }
+        }
+    }
+
+    problems
+}
+
 pub struct Store {
     path: PathBuf,
 }
@@ -41,16 +162,171 @@ impl Store {
         self.path.join(component_dirname(component_name, version))
     }
 
+    /// Like `has_component`, but for the target `cfg` downloads for.
+    pub(crate) fn has_component_for(&self, cfg: &DownloadCfg) -> bool {
+        self.component_dir_path_for(cfg).exists()
+    }
+
+    /// Like `component_dir_path`, but for the target `cfg` downloads for.
+    pub(crate) fn component_dir_path_for(&self, cfg: &DownloadCfg) -> PathBuf {
+        self.path.join(cfg_dirname(cfg))
+    }
+
+    /// Returns the names of the entries in the store, eg. 'forc-0.17.0'. Hidden directories, such
+    /// as those used for staging installs, are not entries.
+    pub(crate) fn entries(&self) -> Result<Vec<String>> {
+        let mut entries = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type()?.is_dir() && !name.starts_with('.') {
+                entries.push(name);
+            }
+        }
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// Returns the store entry that `path` points into, if any.
+    fn entry_of(&self, path: &Path) -> Option<String> {
+        path.strip_prefix(&self.path)
+            .ok()?
+            .components()
+            .next()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+    }
+
+    /// Maps store entries to the toolchains within `toolchains_dir` whose 'bin' directory links
+    /// into them, either through symlinks or hard links. Entries that no toolchain links into are
+    /// left out.
+    pub(crate) fn referencing_toolchains(
+        &self,
+        toolchains_dir: &Path,
+    ) -> Result<BTreeMap<String, BTreeSet<String>>> {
+        let mut references: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        // Hard links are only recognizable by their inode, so these are matched against the
+        // files in the store afterwards.
+        let mut hardlinks: HashMap<(u64, u64), BTreeSet<String>> = HashMap::new();
+
+        if toolchains_dir.is_dir() {
+            for toolchain in fs::read_dir(toolchains_dir)? {
+                let toolchain = toolchain?;
+                let name = toolchain.file_name().to_string_lossy().to_string();
+                // The executables kept from before the last update are referenced as well, so
+                // that the update can still be rolled back.
+                let previous = format!("{name}/{TOOLCHAIN_PREVIOUS_DIR}");
+                let bin_dirs = [
+                    (name, toolchain.path().join("bin")),
+                    (
+                        previous,
+                        toolchain.path().join(TOOLCHAIN_PREVIOUS_DIR).join("bin"),
+                    ),
+                ];
+
+                for (name, bin_dir) in bin_dirs {
+                    let bins = match fs::read_dir(bin_dir) {
+                        Ok(bins) => bins,
+                        Err(_) => continue,
+                    };
+
+                    for bin in bins {
+                        let bin = bin?.path();
+                        let metadata = fs::symlink_metadata(&bin)?;
+                        if metadata.file_type().is_symlink() {
+                            if let Some(entry) = fs::read_link(&bin)
+                                .ok()
+                                .and_then(|target| self.entry_of(&target))
+                            {
+                                references.entry(entry).or_default().insert(name.clone());
+                            }
+                        } else {
+                            hardlinks
+                                .entry((metadata.dev(), metadata.ino()))
+                                .or_default()
+                                .insert(name.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        if !hardlinks.is_empty() {
+            for entry in self.entries()? {
+                for file in fs::read_dir(self.path.join(&entry))? {
+                    let metadata = fs::symlink_metadata(file?.path())?;
+                    if let Some(toolchains) = hardlinks.get(&(metadata.dev(), metadata.ino())) {
+                        references
+                            .entry(entry.clone())
+                            .or_default()
+                            .extend(toolchains.iter().cloned());
+                    }
+                }
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// Returns the store entries, named '<component_name>-<version>', that `cfgs` would have to
+    /// download because they are not in the store yet.
+    pub(crate) fn missing_components(&self, cfgs: &[DownloadCfg]) -> Vec<String> {
+        cfgs.iter()
+            .filter(|cfg| !self.has_component_for(cfg))
+            .map(cfg_dirname)
+            .collect()
+    }
+
+    // In offline mode, components can only be installed from what is already in the store.
+    // This fails with the list of missing store entries if `cfgs` cannot be installed offline.
+    pub(crate) fn ensure_offline_installable(&self, cfgs: &[DownloadCfg]) -> Result<()> {
+        if !is_offline() {
+            return Ok(());
+        }
+
+        let missing = self.missing_components(cfgs);
+        if !missing.is_empty() {
+            bail!(
+                "Cannot install without network access; the following are missing from the store at {}:\n{}",
+                self.path.display(),
+                missing
+                    .iter()
+                    .map(|m| format!("  - {m}\n"))
+                    .collect::<String>()
+            );
+        }
+
+        Ok(())
+    }
+
     // This function installs a component into a directory within '/.fuelup/store'.
-    // The directory is named '<component_name>-<version>', eg. 'fuel-core-0.15.1'.
+    // The directory is named '<component_name>-<version>', eg. 'fuel-core-0.15.1', with the target
+    // appended for components that are not for the host.
+    // An existing directory for the same component version is replaced.
     pub(crate) fn install_component(&self, cfg: &DownloadCfg) -> Result<Vec<PathBuf>> {
-        let component_dir = self.component_dir_path(&cfg.name, &cfg.version);
+        self.install_component_matching(cfg, None)
+    }
+
+    /// Like `install_component`, but if `checksums` are given, as recorded in a store entry's
+    /// checksums file, the new entry only replaces the existing one if its files match them.
+    pub(crate) fn install_component_matching(
+        &self,
+        cfg: &DownloadCfg,
+        checksums: Option<&str>,
+    ) -> Result<Vec<PathBuf>> {
+        let component_dir = self.component_dir_path_for(cfg);
+
+        // The component is downloaded, verified and unpacked in a staging directory, which is only
+        // moved into place once all of that succeeded. This way an interrupted or failed install
+        // never leaves behind a directory that would be mistaken for an installed component.
+        let staging_dir = tempfile::Builder::new()
+            .prefix(STAGING_DIR_PREFIX)
+            .tempdir_in(&self.path)?;
 
         // Cache fuels_version for this component if show_fuels_version exists and is true.
         // We don't want this failure to block installation, so errors are ignored here.
         if let Ok(c) = Component::from_name(&cfg.name) {
             if let Some(true) = c.show_fuels_version {
-                if let Err(e) = self.cache_fuels_version(cfg) {
+                if let Err(e) = self.cache_fuels_version(cfg, staging_dir.path()) {
                     warn!(
                         "Failed to cache fuels version for component '{}': {}",
                         cfg.name, e
@@ -59,24 +335,104 @@ impl Store {
             }
         };
 
-        ensure_dir_exists(&component_dir)?;
-        download_file_and_unpack(cfg, &component_dir)?;
-        // We ensure that component_dir exists above, so its parent must exist here.
-        unpack_bins(&component_dir, &component_dir)
+        download_file_and_unpack(cfg, staging_dir.path())?;
+        let bins = unpack_bins(staging_dir.path(), &component_dir)?;
+
+        // Checksums are only worth recording for binaries that were verified against the channel.
+        if cfg.hash().is_some() {
+            if let Err(e) = write_checksums(staging_dir.path(), &bins) {
+                warn!("Failed to record checksums for '{}': {}", cfg.name, e);
+            }
+        }
+        if let Some(checksums) = checksums {
+            write_file(&staging_dir.path().join(CHECKSUMS_FILE), checksums)?;
+            let problems = verify_component_dir(&cfg_dirname(cfg), staging_dir.path());
+            if !problems.is_empty() {
+                bail!(
+                    "the download does not match the recorded checksums: {}",
+                    problems.join(", ")
+                );
+            }
+        }
+
+        // An existing entry is moved aside into another staging directory rather than removed,
+        // so that it is restored if the new one cannot be moved into place, and removed along with
+        // that directory when it is dropped otherwise.
+        let old_dir = match component_dir.exists() {
+            true => {
+                let old_dir = tempfile::Builder::new()
+                    .prefix(STAGING_DIR_PREFIX)
+                    .tempdir_in(&self.path)?;
+                let old_entry = old_dir.path().join(cfg_dirname(cfg));
+                fs::rename(&component_dir, &old_entry)?;
+                Some((old_dir, old_entry))
+            }
+            false => None,
+        };
+
+        // Once renamed, there is nothing left for the staging directory to clean up when dropped.
+        if let Err(e) = fs::rename(staging_dir.path(), &component_dir) {
+            // Entries are only ever moved into place whole, so if another install of the same
+            // component got there first, its entry is as good as this one.
+            let taken = matches!(
+                e.kind(),
+                io::ErrorKind::AlreadyExists | io::ErrorKind::DirectoryNotEmpty
+            ) && bins.iter().all(|bin| bin.exists());
+            if !taken {
+                if let Some((_, old_entry)) = &old_dir {
+                    fs::rename(old_entry, &component_dir)?;
+                }
+                return Err(e.into());
+            }
+        }
+
+        Ok(bins)
     }
 
-    pub(crate) fn cache_fuels_version(&self, cfg: &DownloadCfg) -> Result<()> {
-        let dirname = component_dirname(&cfg.name, &cfg.version);
-        let component_dir = self.path().join(dirname);
+    /// Installs the components of `cfgs` that are not in the store yet, downloading up to
+    /// `concurrent_downloads()` of them at a time. The results are in the order of `cfgs`, and
+    /// are None for components that were already in the store.
+    pub(crate) fn install_components(
+        &self,
+        cfgs: &[DownloadCfg],
+    ) -> Vec<Result<Option<Vec<PathBuf>>>> {
+        map_concurrently(cfgs, concurrent_downloads(), |cfg| {
+            match self.has_component_for(cfg) {
+                true => Ok(None),
+                false => self.install_component(cfg).map(Some),
+            }
+        })
+    }
 
+    /// Returns the staging directories left behind in the store by installs that were interrupted.
+    pub(crate) fn staging_dirs(&self) -> Result<Vec<PathBuf>> {
+        let mut staging_dirs = vec![];
+        for entry in fs::read_dir(&self.path)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir()
+                && entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(STAGING_DIR_PREFIX)
+            {
+                staging_dirs.push(entry.path());
+            }
+        }
+        Ok(staging_dirs)
+    }
+
+    /// Checks that a store entry has all the executables its component is expected to have, and
+    /// that they still match the checksums recorded when they were installed, if any. Returns a
+    /// description of each problem found.
+    pub(crate) fn verify_entry(&self, entry: &str) -> Vec<String> {
+        verify_component_dir(entry, &self.path.join(entry))
+    }
+
+    fn cache_fuels_version(&self, cfg: &DownloadCfg, component_dir: &Path) -> Result<()> {
         if let Ok(fuels_version) = fetch_fuels_version(cfg) {
-            info!(
-                "Caching fuels version at {}",
-                component_dir.join(FUELS_VERSION_FILE).display()
-            );
-            ensure_dir_exists(&component_dir)?;
-            let mut fuels_version_file =
-                std::fs::File::create(component_dir.join(FUELS_VERSION_FILE))?;
+            let fuels_version_path = component_dir.join(FUELS_VERSION_FILE);
+            info!("Caching fuels version at {}", fuels_version_path.display());
+            let mut fuels_version_file = std::fs::File::create(fuels_version_path)?;
 
             write!(fuels_version_file, "{fuels_version}")?;
         };
@@ -89,8 +445,174 @@ impl Store {
         name: &str,
         version: &Version,
     ) -> std::io::Result<String> {
-        let dirname = component_dirname(name, version);
+        self.get_entry_fuels_version(&component_dirname(name, version))
+    }
+
+    /// Like `get_cached_fuels_version`, but for any store entry.
+    pub(crate) fn get_entry_fuels_version(&self, entry: &str) -> std::io::Result<String> {
+        fs::read_to_string(self.path().join(entry).join(FUELS_VERSION_FILE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{channel::Channel, file::read_file};
+
+    #[test]
+    fn missing_components() -> Result<()> {
+        let store_dir = tempfile::tempdir()?;
+        let store = Store {
+            path: store_dir.path().to_path_buf(),
+        };
+
+        let channel_path = std::env::current_dir()?.join("tests/channel-fuel-latest-example.toml");
+        let channel =
+            Channel::from_toml(&read_file("channel-fuel-latest-example", &channel_path)?)?;
+        let cfgs = channel.build_download_configs(&TargetTriple::from_host()?);
+
+        assert_eq!(
+            store.missing_components(&cfgs),
+            ["forc-0.17.0", "fuel-core-0.9.4"]
+        );
 
-        fs::read_to_string(self.path().join(dirname).join(FUELS_VERSION_FILE))
+        fs::create_dir(store.component_dir_path("forc", &Version::new(0, 17, 0)))?;
+        assert_eq!(store.missing_components(&cfgs), ["fuel-core-0.9.4"]);
+
+        fs::create_dir(store.component_dir_path("fuel-core", &Version::new(0, 9, 4)))?;
+        assert!(store.missing_components(&cfgs).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn map_concurrently_keeps_order() {
+        let items: Vec<u64> = (0..16).collect();
+        for jobs in [0, 1, 4, 32] {
+            let results = map_concurrently(&items, jobs, |i| {
+                // Make later items finish first.
+                thread::sleep(std::time::Duration::from_millis(16 - i));
+                i * 2
+            });
+            assert_eq!(results, items.iter().map(|i| i * 2).collect::<Vec<_>>());
+        }
+        assert!(map_concurrently(&Vec::<u64>::new(), 4, |i| *i).is_empty());
+    }
+
+    #[test]
+    fn staging_dirs_are_not_entries() -> Result<()> {
+        let store_dir = tempfile::tempdir()?;
+        let store = Store {
+            path: store_dir.path().to_path_buf(),
+        };
+        fs::create_dir(store.path().join("forc-0.17.0"))?;
+        fs::create_dir(store.path().join(".tmp-abc123"))?;
+
+        assert_eq!(store.entries()?, ["forc-0.17.0"]);
+        assert_eq!(store.staging_dirs()?, [store.path().join(".tmp-abc123")]);
+        assert!(!store.has_component("forc", &Version::new(0, 18, 0)));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_store_entry_names() {
+        assert_eq!(
+            parse_component_dirname("forc-0.17.0"),
+            Some(("forc", Version::new(0, 17, 0), None))
+        );
+        assert_eq!(
+            parse_component_dirname("fuel-core-0.9.4"),
+            Some(("fuel-core", Version::new(0, 9, 4), None))
+        );
+        assert_eq!(
+            parse_component_dirname("forc-wallet-0.2.0-rc.1"),
+            Some(("forc-wallet", Version::parse("0.2.0-rc.1").unwrap(), None))
+        );
+        assert_eq!(
+            parse_component_dirname("forc-0.17.0-linux_arm64"),
+            Some((
+                "forc",
+                Version::new(0, 17, 0),
+                Some(TargetTriple::from_component_target("forc", "linux_arm64").unwrap())
+            ))
+        );
+        assert_eq!(
+            parse_component_dirname("fuel-core-0.9.4-rc.1-aarch64-apple-darwin"),
+            Some((
+                "fuel-core",
+                Version::parse("0.9.4-rc.1").unwrap(),
+                Some(
+                    TargetTriple::from_component_target("fuel-core", "aarch64-apple-darwin")
+                        .unwrap()
+                )
+            ))
+        );
+        assert_eq!(parse_component_dirname("forc"), None);
+        assert_eq!(parse_component_dirname("fuel-core-latest"), None);
+    }
+
+    #[test]
+    fn verify_entry() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let store_dir = tempfile::tempdir()?;
+        let store = Store {
+            path: store_dir.path().to_path_buf(),
+        };
+        let entry_dir = store.component_dir_path("fuel-core", &Version::new(0, 9, 4));
+        fs::create_dir(&entry_dir)?;
+        assert_eq!(
+            store.verify_entry("fuel-core-0.9.4"),
+            ["missing executable 'fuel-core'"]
+        );
+
+        let bin = entry_dir.join("fuel-core");
+        fs::write(&bin, "fuel-core")?;
+        assert_eq!(
+            store.verify_entry("fuel-core-0.9.4"),
+            ["'fuel-core' is not executable"]
+        );
+
+        fs::set_permissions(&bin, fs::Permissions::from_mode(0o755))?;
+        write_checksums(&entry_dir, std::slice::from_ref(&bin))?;
+        assert!(store.verify_entry("fuel-core-0.9.4").is_empty());
+
+        fs::write(&bin, "tampered")?;
+        assert_eq!(
+            store.verify_entry("fuel-core-0.9.4"),
+            ["checksum mismatch for 'fuel-core'"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn referencing_toolchains() -> Result<()> {
+        let fuelup_dir = tempfile::tempdir()?;
+        let store = Store {
+            path: fuelup_dir.path().join("store"),
+        };
+        let toolchains_dir = fuelup_dir.path().join("toolchains");
+
+        for entry in ["forc-0.1.0", "fuel-core-0.1.0", "fuel-core-0.2.0"] {
+            fs::create_dir_all(store.path().join(entry))?;
+        }
+        fs::write(store.path().join("forc-0.1.0/forc"), "forc")?;
+        fs::write(store.path().join("fuel-core-0.1.0/fuel-core"), "fuel-core")?;
+
+        let bin_dir = toolchains_dir.join("my-toolchain/bin");
+        fs::create_dir_all(&bin_dir)?;
+        fs::hard_link(store.path().join("forc-0.1.0/forc"), bin_dir.join("forc"))?;
+        std::os::unix::fs::symlink(
+            store.path().join("fuel-core-0.1.0/fuel-core"),
+            bin_dir.join("fuel-core"),
+        )?;
+
+        let references = store.referencing_toolchains(&toolchains_dir)?;
+        assert_eq!(
+            references.keys().collect::<Vec<_>>(),
+            ["forc-0.1.0", "fuel-core-0.1.0"]
+        );
+        assert!(references["forc-0.1.0"].contains("my-toolchain"));
+        assert!(references["fuel-core-0.1.0"].contains("my-toolchain"));
+        Ok(())
     }
 }
@@ -58,15 +58,41 @@ impl TargetTriple {
         Ok(Self(target_triple))
     }
 
+    /// Returns the host's target in the naming scheme used by `component`'s release tarballs.
     pub fn from_component(component: &str) -> Result<Self> {
+        Self::from_host()?.for_component(component)
+    }
+
+    /// Parses a target in the naming scheme used by `component`'s release tarballs, which has to
+    /// be one of the component's targets in components.toml.
+    pub fn from_component_target(component: &str, target: &str) -> Result<Self> {
+        if Component::from_name(component)?
+            .targets
+            .iter()
+            .any(|t| t == target)
+        {
+            Ok(Self(target.to_string()))
+        } else {
+            bail!("Unsupported target for '{}': '{}'", component, target)
+        }
+    }
+
+    /// Converts this target into the naming scheme used by `component`'s release tarballs, which
+    /// is eg. 'linux_amd64' for forc rather than 'x86_64-unknown-linux-gnu'.
+    pub fn for_component(&self, component: &str) -> Result<Self> {
         match Component::from_name(component).map(|c| c.name)?.as_str() {
             component::FORC => {
-                let os = match std::env::consts::OS {
-                    "macos" => "darwin",
-                    "linux" => "linux",
-                    unsupported_os => bail!("Unsupported os: {}", unsupported_os),
+                let (architecture, os) = match self.0.split_once('-') {
+                    Some((architecture, rest)) => (architecture, rest.split_once('-').map(|r| r.1)),
+                    None => bail!("missing vendor-os specifier"),
                 };
-                let architecture = match std::env::consts::ARCH {
+                let os = match os {
+                    Some("darwin") => "darwin",
+                    Some("linux-gnu") => "linux",
+                    Some(unsupported_os) => bail!("Unsupported os: {}", unsupported_os),
+                    None => bail!("missing os specifier"),
+                };
+                let architecture = match architecture {
                     "aarch64" => "arm64",
                     "x86_64" => "amd64",
                     unsupported_arch => bail!("Unsupported architecture: {}", unsupported_arch),
@@ -74,25 +100,30 @@ impl TargetTriple {
 
                 Ok(Self(format!("{os}_{architecture}")))
             }
-            _ => {
-                let architecture = match std::env::consts::ARCH {
-                    "aarch64" | "x86_64" => std::env::consts::ARCH,
-                    unsupported_arch => bail!("Unsupported architecture: {}", unsupported_arch),
-                };
-
-                let vendor = match std::env::consts::OS {
-                    "macos" => "apple",
-                    _ => "unknown",
-                };
-
-                let os = match std::env::consts::OS {
-                    "macos" => "darwin",
-                    "linux" => "linux-gnu",
-                    unsupported_os => bail!("Unsupported os: {}", unsupported_os),
-                };
-
-                Ok(Self(format!("{architecture}-{vendor}-{os}")))
-            }
+            _ => Ok(self.clone()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_for_component() -> Result<()> {
+        let target = TargetTriple::new("aarch64-unknown-linux-gnu")?;
+        assert_eq!(target.for_component("forc")?.to_string(), "linux_arm64");
+        assert_eq!(
+            target.for_component("fuel-core")?.to_string(),
+            "aarch64-unknown-linux-gnu"
+        );
+
+        let target = TargetTriple::new("x86_64-apple-darwin")?;
+        assert_eq!(target.for_component("forc")?.to_string(), "darwin_amd64");
+        assert!(target.for_component("not-a-component").is_err());
+
+        assert!(TargetTriple::from_component_target("forc", "darwin_amd64").is_ok());
+        assert!(TargetTriple::from_component_target("forc", "x86_64-apple-darwin").is_err());
+        Ok(())
+    }
+}
@@ -1,17 +1,20 @@
 use anyhow::{bail, Context, Result};
 use component::{self, Components};
+use semver::Version;
+use std::env;
 use std::fmt;
-use std::fs::{remove_dir_all, remove_file};
-use std::path::PathBuf;
+use std::fs::{self, remove_dir_all, remove_file};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
 use time::Date;
 use tracing::{error, info};
 
 use crate::channel::{self, is_beta_toolchain, Channel};
-use crate::constants::DATE_FORMAT;
+use crate::constants::{DATE_FORMAT, TOOLCHAIN_PREVIOUS_DIR};
 use crate::download::DownloadCfg;
 use crate::file::{hard_or_symlink_file, is_executable};
+use crate::lock::FuelupLock;
 use crate::ops::fuelup_self::self_update;
 use crate::path::{
     ensure_dir_exists, fuelup_bin, fuelup_bin_dir, fuelup_tmp_dir, settings_file,
@@ -20,6 +23,12 @@ use crate::path::{
 use crate::settings::SettingsFile;
 use crate::store::Store;
 use crate::target_triple::TargetTriple;
+use crate::toolchain_manifest::{ComponentManifest, ToolchainManifest};
+use crate::toolchain_override::ToolchainOverride;
+
+/// Environment variable naming the toolchain to use, taking precedence over directory overrides,
+/// fuel-toolchain.toml and the default toolchain.
+pub const FUELUP_TOOLCHAIN: &str = "FUELUP_TOOLCHAIN";
 
 pub const RESERVED_TOOLCHAIN_NAMES: &[&str] = &[
     channel::LATEST,
@@ -88,11 +97,14 @@ fn parse_metadata(metadata: String) -> Result<(Option<Date>, Option<TargetTriple
             if second.is_empty() {
                 Ok((Some(d), None))
             } else {
-                let target = second.trim_start_matches('-');
-                bail!(
-                    "You specified target '{}': specifying a target is not supported yet.",
-                    target
-                );
+                let target = match second.strip_prefix('-') {
+                    Some(target) => target,
+                    None => bail!("Failed to parse date"),
+                };
+                match TargetTriple::new(target) {
+                    Ok(t) => Ok((Some(d), Some(t))),
+                    Err(e) => bail!("Invalid target '{}': {}", target, e),
+                }
             }
         }
         Err(_) => match TargetTriple::new(&metadata) {
@@ -102,9 +114,19 @@ fn parse_metadata(metadata: String) -> Result<(Option<Date>, Option<TargetTriple
     }
 }
 
+impl DistToolchainDescription {
+    /// The target this toolchain is for, which is the host unless a target was specified.
+    pub fn target(&self) -> Result<TargetTriple> {
+        match &self.target {
+            Some(target) => Ok(target.clone()),
+            None => TargetTriple::from_host(),
+        }
+    }
+}
+
 impl fmt::Display for DistToolchainDescription {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let target = TargetTriple::from_host().unwrap_or_default();
+        let target = self.target().unwrap_or_default();
         match self.date {
             Some(d) => write!(f, "{}-{}-{}", self.name, d, target),
             None => write!(f, "{}-{}", self.name, target),
@@ -150,7 +172,20 @@ impl FromStr for DistToolchainDescription {
     }
 }
 
-fn cache_sway_std_libs(forc_bin_path: PathBuf) -> Result<()> {
+fn ensure_fuelup_installed() -> Result<()> {
+    ensure_dir_exists(&fuelup_bin_dir())?;
+
+    if !fuelup_bin().is_file() {
+        info!("fuelup not found - attempting to self update");
+        match self_update() {
+            Ok(()) => info!("fuelup installed."),
+            Err(e) => bail!("Could not install fuelup: {}", e),
+        };
+    }
+    Ok(())
+}
+
+pub(crate) fn cache_sway_std_libs(forc_bin_path: PathBuf) -> Result<()> {
     let fuelup_tmp_dir = fuelup_tmp_dir();
     ensure_dir_exists(&fuelup_tmp_dir)?;
     let temp_project = tempfile::Builder::new().prefix("temp-project").tempdir()?;
@@ -175,6 +210,23 @@ fn cache_sway_std_libs(forc_bin_path: PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Where the active toolchain was determined from. The variants are listed in order of
+/// precedence, so a directory override beats a fuel-toolchain.toml, which beats the default.
+#[derive(Debug)]
+pub enum ToolchainSource {
+    /// A toolchain given on the command line, eg. `forc +nightly build` or
+    /// `fuelup run nightly forc build`.
+    CommandLine,
+    /// The `FUELUP_TOOLCHAIN` environment variable.
+    Environment,
+    /// An override set with `fuelup override set` for this directory or one of its parents.
+    DirectoryOverride(PathBuf),
+    /// The fuel-toolchain.toml of the project the current directory is in.
+    ToolchainFile(ToolchainOverride),
+    /// The default toolchain from the settings.
+    Default,
+}
+
 #[derive(Debug)]
 pub struct Toolchain {
     pub name: String,
@@ -217,6 +269,78 @@ impl Toolchain {
         bail!("No default toolchain detected. Please install or create a toolchain first.")
     }
 
+    /// Returns the toolchain named `name`, which is either a distributable toolchain description
+    /// like 'nightly' or the name of a custom toolchain.
+    pub fn from_name(name: &str) -> Self {
+        match DistToolchainDescription::from_str(name) {
+            Ok(desc) => Self::from_path(&desc.to_string()),
+            Err(_) => Self::from_path(name),
+        }
+    }
+
+    /// Returns the toolchain named by the `FUELUP_TOOLCHAIN` environment variable, if it is set.
+    pub fn from_environment() -> Result<Option<Self>> {
+        match env::var(FUELUP_TOOLCHAIN).ok().filter(|s| !s.is_empty()) {
+            Some(name) => {
+                let toolchain = Self::from_name(&name);
+                if !toolchain.exists() {
+                    bail!(
+                        "toolchain '{}' set by {} is not installed",
+                        toolchain.name,
+                        FUELUP_TOOLCHAIN
+                    );
+                }
+                Ok(Some(toolchain))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the toolchain named by `FUELUP_TOOLCHAIN`, falling back to the default toolchain.
+    pub fn from_environment_or_settings() -> Result<Self> {
+        match Self::from_environment()? {
+            Some(toolchain) => Ok(toolchain),
+            None => Self::from_settings(),
+        }
+    }
+
+    /// Determines the toolchain to use in the current directory, along with where it was
+    /// determined from. See `ToolchainSource` for the order of precedence.
+    pub fn from_active() -> Result<(Self, ToolchainSource)> {
+        if let Some(toolchain) = Self::from_environment()? {
+            return Ok((toolchain, ToolchainSource::Environment));
+        }
+
+        if settings_file().exists() {
+            let current_dir = env::current_dir()?;
+            let directory_override =
+                SettingsFile::new(settings_file()).with(|s| Ok(s.find_override(&current_dir)))?;
+            if let Some((dir, name)) = directory_override {
+                let toolchain = Self::from_path(&name);
+                if !toolchain.exists() {
+                    bail!(
+                        "toolchain '{}' set as the override for '{}' is not installed; install it with `fuelup toolchain install {}` or remove the override with `fuelup override unset`",
+                        toolchain.name,
+                        dir.display(),
+                        toolchain.name
+                    );
+                }
+                return Ok((toolchain, ToolchainSource::DirectoryOverride(dir)));
+            }
+        }
+
+        if let Some(to) = ToolchainOverride::from_project_root() {
+            let channel = to.cfg.toolchain.channel.to_string();
+            let name = match DistToolchainDescription::from_str(&channel) {
+                Ok(desc) => desc.to_string(),
+                Err(_) => channel,
+            };
+            return Ok((Self::from_path(&name), ToolchainSource::ToolchainFile(to)));
+        }
+
+        Ok((Self::from_settings()?, ToolchainSource::Default))
+    }
+
     pub fn is_distributed(&self) -> bool {
         RESERVED_TOOLCHAIN_NAMES.contains(&self.name.split_once('-').unwrap_or((&self.name, "")).0)
     }
@@ -225,6 +349,15 @@ impl Toolchain {
         self.path.exists() && self.path.is_dir()
     }
 
+    /// Whether this is a custom toolchain created with `fuelup toolchain link`, whose bin
+    /// directory is a link to a local directory.
+    pub fn is_linked(&self) -> bool {
+        self.bin_path
+            .symlink_metadata()
+            .map(|m| m.file_type().is_symlink())
+            .unwrap_or(false)
+    }
+
     pub fn has_component(&self, component: &str) -> bool {
         if let Some(component) = Components::collect()
             .expect("Failed to collect components")
@@ -248,78 +381,109 @@ impl Toolchain {
     pub fn add_component(&self, download_cfg: DownloadCfg) -> Result<DownloadCfg> {
         // Pre-install checks: ensuring toolchain dir, fuelup bin dir, and fuelup exist
         ensure_dir_exists(&self.bin_path)?;
+        ensure_fuelup_installed()?;
 
-        let fuelup_bin_dir = fuelup_bin_dir();
-        ensure_dir_exists(&fuelup_bin_dir)?;
+        let store = Store::from_env()?;
+        let downloaded = match store.has_component_for(&download_cfg) {
+            true => None,
+            false => Some(store.install_component(&download_cfg)),
+        };
+        self.link_component(&store, download_cfg, downloaded)
+    }
 
-        let fuelup_bin = fuelup_bin();
-        if !fuelup_bin.is_file() {
-            info!("fuelup not found - attempting to self update");
-            match self_update() {
-                Ok(()) => info!("fuelup installed."),
-                Err(e) => bail!("Could not install fuelup: {}", e),
-            };
-        }
+    /// Like `add_component`, but the components that are missing from the store are downloaded
+    /// concurrently. They are then added to the toolchain in the order of `cfgs`, which is also
+    /// the order of the results.
+    pub fn add_components(&self, cfgs: Vec<DownloadCfg>) -> Result<Vec<Result<DownloadCfg>>> {
+        ensure_dir_exists(&self.bin_path)?;
+        ensure_fuelup_installed()?;
 
         let store = Store::from_env()?;
+        let downloads = store.install_components(&cfgs);
+        Ok(cfgs
+            .into_iter()
+            .zip(downloads)
+            .map(|(cfg, downloaded)| self.link_component(&store, cfg, downloaded.transpose()))
+            .collect())
+    }
+
+    /// Links the executables of a component into the toolchain. `downloaded` holds the result of
+    /// installing the component into the store, or is None if it already was in the store.
+    fn link_component(
+        &self,
+        store: &Store,
+        download_cfg: DownloadCfg,
+        downloaded: Option<Result<Vec<PathBuf>>>,
+    ) -> Result<DownloadCfg> {
+        let fuelup_bin_dir = fuelup_bin_dir();
+        let fuelup_bin = fuelup_bin();
 
         info!(
             "\nAdding component {} v{} to '{}'",
             &download_cfg.name, &download_cfg.version, self.name
         );
 
-        if !store.has_component(&download_cfg.name, &download_cfg.version) {
-            match store.install_component(&download_cfg) {
-                Ok(downloaded) => {
-                    for bin in downloaded {
-                        if is_executable(bin.as_path()) {
-                            if let Some(exe_file_name) = bin.file_name() {
-                                // Link binary in store -> binary in the toolchain dir
+        let mut executables = Vec::new();
+        match downloaded {
+            Some(Ok(downloaded)) => {
+                for bin in downloaded {
+                    if is_executable(bin.as_path()) {
+                        if let Some(exe_file_name) = bin.file_name() {
+                            executables.push(exe_file_name.to_string_lossy().to_string());
+                            // Link binary in store -> binary in the toolchain dir
+                            hard_or_symlink_file(
+                                bin.as_path(),
+                                &self.bin_path.join(exe_file_name),
+                            )?;
+                            if !fuelup_bin_dir.join(exe_file_name).exists() {
+                                // Link real 'fuelup' bin -> fake 'fuelup' that acts as
+                                // the installed component in ~/.fuelup/bin, eg. 'forc'
                                 hard_or_symlink_file(
-                                    bin.as_path(),
-                                    &self.bin_path.join(exe_file_name),
+                                    &fuelup_bin,
+                                    &fuelup_bin_dir.join(exe_file_name),
                                 )?;
-                                if !fuelup_bin_dir.join(exe_file_name).exists() {
-                                    // Link real 'fuelup' bin -> fake 'fuelup' that acts as
-                                    // the installed component in ~/.fuelup/bin, eg. 'forc'
-                                    hard_or_symlink_file(
-                                        &fuelup_bin,
-                                        &fuelup_bin_dir.join(exe_file_name),
-                                    )?;
-                                }
                             }
                         }
                     }
-
-                    // Little hack here to download core and std lib upon installing `forc`
-                    if download_cfg.name == component::FORC {
-                        cache_sway_std_libs(self.bin_path.join(component::FORC))?;
-                    };
                 }
-                Err(e) => bail!(
-                    "Could not add component {}({}): {}",
-                    &download_cfg.name,
-                    &download_cfg.version,
-                    e
-                ),
-            }
-        } else {
-            // We have to iterate here because `fuelup component add forc` has to account for
-            // other built-in plugins as well, eg. forc-fmt
-            for entry in std::fs::read_dir(
-                store.component_dir_path(&download_cfg.name, &download_cfg.version),
-            )? {
-                let entry = entry?;
-                let exe = entry.path();
 
-                if is_executable(exe.as_path()) {
-                    if let Some(exe_file_name) = exe.file_name() {
-                        hard_or_symlink_file(exe.as_path(), &self.bin_path.join(exe_file_name))?;
+                // Little hack here to download core and std lib upon installing `forc`,
+                // which is only possible if it can run on the host.
+                if download_cfg.name == component::FORC
+                    && TargetTriple::from_component(component::FORC)? == download_cfg.target
+                {
+                    cache_sway_std_libs(self.bin_path.join(component::FORC))?;
+                };
+            }
+            Some(Err(e)) => bail!(
+                "Could not add component {}({}): {}",
+                &download_cfg.name,
+                &download_cfg.version,
+                e
+            ),
+            None => {
+                // We have to iterate here because `fuelup component add forc` has to account for
+                // other built-in plugins as well, eg. forc-fmt
+                for entry in std::fs::read_dir(store.component_dir_path_for(&download_cfg))? {
+                    let entry = entry?;
+                    let exe = entry.path();
+
+                    if is_executable(exe.as_path()) {
+                        if let Some(exe_file_name) = exe.file_name() {
+                            executables.push(exe_file_name.to_string_lossy().to_string());
+                            hard_or_symlink_file(
+                                exe.as_path(),
+                                &self.bin_path.join(exe_file_name),
+                            )?;
+                        }
                     }
                 }
             }
         };
 
+        executables.sort();
+        self.record_components(store, [(&download_cfg, executables)])?;
+
         info!(
             "Installed {} v{} for toolchain '{}'",
             download_cfg.name, download_cfg.version, self.name
@@ -329,31 +493,186 @@ impl Toolchain {
     }
 
     pub fn install_if_nonexistent(&self, description: &DistToolchainDescription) -> Result<()> {
+        if self.exists() {
+            return Ok(());
+        }
+
+        // Another process may have installed the toolchain while we were waiting for the lock.
+        let _lock = FuelupLock::acquire()?;
         if !self.exists() {
             info!("toolchain '{}' does not exist; installing", description);
-            if let Ok(channel) = Channel::from_dist_channel(description) {
-                ensure_dir_exists(&self.bin_path)?;
-                let store = Store::from_env()?;
-                for cfg in channel.build_download_configs() {
-                    if store.has_component(&cfg.name, &cfg.version) {
-                        hard_or_symlink_file(
-                            &store
-                                .component_dir_path(&cfg.name, &cfg.version)
-                                .join(&cfg.name),
-                            &self.bin_path.join(&cfg.name),
-                        )?;
-                    } else {
-                        let downloaded = store.install_component(&cfg)?;
+            let channel = Channel::from_dist_channel(description)?;
+            let cfgs = channel.build_download_configs(&description.target()?);
+            let store = Store::from_env()?;
+            store.ensure_offline_installable(&cfgs)?;
+
+            ensure_dir_exists(&self.bin_path)?;
+            for (cfg, downloaded) in cfgs.iter().zip(store.install_components(&cfgs)) {
+                match downloaded? {
+                    None => hard_or_symlink_file(
+                        &store.component_dir_path_for(cfg).join(&cfg.name),
+                        &self.bin_path.join(&cfg.name),
+                    )?,
+                    Some(downloaded) => {
                         for bin in downloaded {
                             hard_or_symlink_file(&bin, &self.bin_path.join(&cfg.name))?;
                         }
                     }
                 }
             }
+            self.record_components(&store, cfgs.iter().map(|cfg| (cfg, vec![cfg.name.clone()])))?;
         };
 
         Ok(())
     }
+    /// Replaces all of the toolchain's components with `cfgs`, which must already be in the store.
+    /// The executables and manifest being replaced are kept, so that `rollback` can restore them.
+    pub fn replace_components(&self, store: &Store, cfgs: &[DownloadCfg]) -> Result<()> {
+        ensure_fuelup_installed()?;
+        let fuelup_bin_dir = fuelup_bin_dir();
+        let fuelup_bin = fuelup_bin();
+
+        // The new executables are linked into a separate directory first, so that the toolchain
+        // is left untouched if that fails.
+        ensure_dir_exists(&self.path)?;
+        let staging = tempfile::Builder::new()
+            .prefix(".update-")
+            .tempdir_in(&self.path)?;
+        let bin_dir = staging.path().join("bin");
+        ensure_dir_exists(&bin_dir)?;
+
+        let mut manifest = ToolchainManifest::default();
+        for cfg in cfgs {
+            let component_dir = store.component_dir_path_for(cfg);
+            let mut executables = Vec::new();
+            for entry in fs::read_dir(&component_dir)? {
+                let exe = entry?.path();
+                if is_executable(exe.as_path()) {
+                    if let Some(exe_file_name) = exe.file_name() {
+                        executables.push(exe_file_name.to_string_lossy().to_string());
+                        hard_or_symlink_file(exe.as_path(), &bin_dir.join(exe_file_name))?;
+                        if !fuelup_bin_dir.join(exe_file_name).exists() {
+                            hard_or_symlink_file(&fuelup_bin, &fuelup_bin_dir.join(exe_file_name))?;
+                        }
+                    }
+                }
+            }
+            executables.sort();
+            manifest.components.insert(
+                cfg.name.clone(),
+                ComponentManifest::new(cfg, component_dir, executables),
+            );
+        }
+        manifest.write(staging.path())?;
+
+        self.replace_bin_dir(staging.path())
+    }
+
+    /// Restores the executables and manifest the toolchain had before its last update. The ones
+    /// being replaced are kept in turn, so rolling back again undoes the rollback.
+    pub fn rollback(&self) -> Result<()> {
+        let previous = self.path.join(TOOLCHAIN_PREVIOUS_DIR);
+        if !previous.join("bin").is_dir() {
+            bail!(
+                "toolchain '{}' has no previous installation to roll back to",
+                self.name
+            );
+        }
+
+        let restoring = self.path.join(".rollback");
+        if restoring.exists() {
+            remove_dir_all(&restoring)?;
+        }
+        fs::rename(&previous, &restoring)?;
+        self.replace_bin_dir(&restoring)
+    }
+
+    /// Makes the bin directory and manifest within `dir` the toolchain's own, and moves the
+    /// replaced ones into the toolchain's previous installation directory.
+    fn replace_bin_dir(&self, dir: &Path) -> Result<()> {
+        let previous = self.path.join(TOOLCHAIN_PREVIOUS_DIR);
+        if previous.exists() {
+            remove_dir_all(&previous)?;
+        }
+        ensure_dir_exists(&previous)?;
+
+        let manifest = ToolchainManifest::path(&self.path);
+        if self.bin_path.exists() {
+            fs::rename(&self.bin_path, previous.join("bin"))?;
+        }
+        if manifest.is_file() {
+            fs::rename(&manifest, ToolchainManifest::path(&previous))?;
+        }
+
+        fs::rename(dir.join("bin"), &self.bin_path)?;
+        let new_manifest = ToolchainManifest::path(dir);
+        if new_manifest.is_file() {
+            fs::rename(new_manifest, &manifest)?;
+        }
+        remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    /// Records the given components and the executables linked from them in the toolchain's
+    /// manifest.
+    fn record_components<'a>(
+        &self,
+        store: &Store,
+        components: impl IntoIterator<Item = (&'a DownloadCfg, Vec<String>)>,
+    ) -> Result<()> {
+        let mut manifest = ToolchainManifest::from_toolchain_dir(&self.path)
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        for (cfg, executables) in components {
+            let store_path = store.component_dir_path_for(cfg);
+            manifest.components.insert(
+                cfg.name.clone(),
+                ComponentManifest::new(cfg, store_path, executables),
+            );
+        }
+        manifest.write(&self.path)
+    }
+
+    fn forget_component(&self, component: &str) -> Result<()> {
+        if let Some(mut manifest) = ToolchainManifest::from_toolchain_dir(&self.path)? {
+            if manifest.components.remove(component).is_some() {
+                manifest.write(&self.path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the version of one of the toolchain's executables, as recorded in the toolchain's
+    /// manifest. Executables missing from the manifest are run with `--version` instead.
+    pub fn executable_version(&self, executable: &str) -> Result<Version> {
+        let path = self.bin_path.join(executable);
+        if path.is_file() {
+            if let Ok(Some(manifest)) = ToolchainManifest::from_toolchain_dir(&self.path) {
+                if let Some(version) = manifest.version_of(executable) {
+                    return Ok(version.clone());
+                }
+            }
+        }
+
+        match Command::new(&path).arg("--version").output() {
+            Ok(o) => {
+                let output = String::from_utf8_lossy(&o.stdout).into_owned();
+                match output.split_whitespace().last() {
+                    Some(v) => Ok(Version::parse(v)?),
+                    None => bail!("Error getting version string"),
+                }
+            }
+            Err(e) => {
+                if path.exists() {
+                    bail!("execution error - {}", e);
+                } else {
+                    bail!("not found");
+                }
+            }
+        }
+    }
+
     fn remove_executables(&self, component: &str) -> Result<()> {
         let executables = &Components::collect().unwrap().component[component].executables;
         for executable in executables {
@@ -367,7 +686,10 @@ impl Toolchain {
         if self.can_remove(component) {
             if self.has_component(component) {
                 info!("Removing '{}' from toolchain '{}'", component, self.name);
-                match self.remove_executables(component) {
+                match self
+                    .remove_executables(component)
+                    .and_then(|_| self.forget_component(component))
+                {
                     Ok(_) => info!("'{}' removed from toolchain '{}'", component, self.name),
                     Err(e) => error!(
                         "Failed to remove '{}' from toolchain '{}': {}",
@@ -447,15 +769,15 @@ mod tests {
             TARGET_X86_LINUX,
         ] {
             let toolchain = format!("{}-{}-{}", channel::NIGHTLY.to_owned(), DATE, target);
-            assert!(DistToolchainDescription::from_str(&toolchain).is_err());
-            // TODO: Uncomment once target specification is supported
-            // see issue #237: https://github.com/FuelLabs/fuelup/issues/237
-            // assert_eq!(
-            //     desc.name,
-            //     DistToolchainName::from_str(channel::NIGHTLY).unwrap()
-            // );
-            // assert_eq!(desc.date.unwrap().to_string(), DATE);
-            // assert_eq!(desc.target.unwrap().to_string(), target);
+            let desc = DistToolchainDescription::from_str(&toolchain).unwrap();
+
+            assert_eq!(
+                desc.name,
+                DistToolchainName::from_str(channel::NIGHTLY).unwrap()
+            );
+            assert_eq!(desc.date.unwrap().to_string(), DATE);
+            assert_eq!(desc.target.as_ref().unwrap().to_string(), target);
+            assert_eq!(desc.to_string(), toolchain);
         }
 
         Ok(())
@@ -475,7 +797,8 @@ mod tests {
 
                 assert_eq!(desc.name, DistToolchainName::from_str(name).unwrap());
                 assert!(desc.date.is_none());
-                assert_eq!(desc.target.unwrap().to_string(), target);
+                assert_eq!(desc.target.as_ref().unwrap().to_string(), target);
+                assert_eq!(desc.to_string(), toolchain);
             }
         }
 
@@ -491,20 +814,66 @@ mod tests {
 
     #[test]
     fn test_parse_metadata_date_target() -> Result<()> {
-        assert!(parse_metadata(DATE_TARGET_APPLE.to_string()).is_err());
-        // TODO: Uncomment once target specification is supported
-        // see issue #237: https://github.com/FuelLabs/fuelup/issues/237
-        //assert_eq!(DATE, date.unwrap().to_string());
-        //assert_eq!(TARGET_X86_APPLE, target.unwrap().to_string());
+        let (date, target) = parse_metadata(DATE_TARGET_APPLE.to_string())?;
+        assert_eq!(DATE, date.unwrap().to_string());
+        assert_eq!(TARGET_X86_APPLE, target.unwrap().to_string());
         Ok(())
     }
 
     #[test]
     fn test_parse_metadata_should_fail() -> Result<()> {
-        const INPUTS: &[&str] = &["2022", "2022-8-1", "2022-8", "2022-8-x86_64-apple-darwin"];
+        const INPUTS: &[&str] = &[
+            "2022",
+            "2022-8-1",
+            "2022-8",
+            "2022-8-x86_64-apple-darwin",
+            "2022-08-29-",
+            "2022-08-29x86_64-apple-darwin",
+            "2022-08-29-x86_64-pc-windows",
+        ];
         for input in INPUTS {
             assert!(parse_metadata(input.to_string()).is_err());
         }
         Ok(())
     }
+
+    #[test]
+    fn test_replace_bin_dir_and_rollback() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let toolchain = Toolchain {
+            name: "latest".to_string(),
+            path: dir.path().to_path_buf(),
+            bin_path: dir.path().join("bin"),
+        };
+        fs::create_dir(&toolchain.bin_path)?;
+        fs::write(toolchain.bin_path.join("forc"), "old")?;
+
+        assert!(toolchain.rollback().is_err());
+
+        let staging = dir.path().join(".update-test");
+        fs::create_dir_all(staging.join("bin"))?;
+        fs::write(staging.join("bin").join("forc"), "new")?;
+        ToolchainManifest::default().write(&staging)?;
+        toolchain.replace_bin_dir(&staging)?;
+
+        let previous = dir.path().join(TOOLCHAIN_PREVIOUS_DIR);
+        assert!(!staging.exists());
+        assert_eq!(fs::read_to_string(toolchain.bin_path.join("forc"))?, "new");
+        assert!(ToolchainManifest::path(&toolchain.path).is_file());
+        assert_eq!(
+            fs::read_to_string(previous.join("bin").join("forc"))?,
+            "old"
+        );
+        assert!(!ToolchainManifest::path(&previous).exists());
+
+        toolchain.rollback()?;
+        assert_eq!(fs::read_to_string(toolchain.bin_path.join("forc"))?, "old");
+        assert!(!ToolchainManifest::path(&toolchain.path).exists());
+        assert_eq!(
+            fs::read_to_string(previous.join("bin").join("forc"))?,
+            "new"
+        );
+        assert!(ToolchainManifest::path(&previous).is_file());
+        Ok(())
+    }
 }
@@ -0,0 +1,113 @@
+use anyhow::{Context, Result};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use time::OffsetDateTime;
+use toml_edit::{de, ser};
+
+use crate::{constants::TOOLCHAIN_MANIFEST_FILE, download::DownloadCfg, file};
+
+// Representation of the 'manifest.toml' within a toolchain directory, which records what was
+// installed into the toolchain so that it can be inspected without running its executables.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ToolchainManifest {
+    #[serde(default)]
+    pub components: BTreeMap<String, ComponentManifest>,
+}
+
+// Represents a [components.<name>] table in 'manifest.toml'.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ComponentManifest {
+    pub version: Version,
+    pub executables: Vec<String>,
+    pub store_path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub installed_at: OffsetDateTime,
+}
+
+impl ComponentManifest {
+    pub fn new(cfg: &DownloadCfg, store_path: PathBuf, executables: Vec<String>) -> Self {
+        Self {
+            version: cfg.version.clone(),
+            executables,
+            store_path,
+            channel: cfg.channel().map(String::from),
+            hash: cfg.hash().map(String::from),
+            installed_at: OffsetDateTime::now_utc(),
+        }
+    }
+}
+
+impl ToolchainManifest {
+    pub fn path(toolchain_dir: &Path) -> PathBuf {
+        toolchain_dir.join(TOOLCHAIN_MANIFEST_FILE)
+    }
+
+    /// Reads the manifest of the toolchain at `toolchain_dir`, if it has one. Toolchains installed
+    /// by older versions of fuelup, and linked toolchains, do not.
+    pub fn from_toolchain_dir(toolchain_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(toolchain_dir);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let toml = file::read_file("toolchain manifest", &path)?;
+        let manifest = de::from_str(&toml)
+            .with_context(|| format!("Invalid toolchain manifest {}", path.display()))?;
+        Ok(Some(manifest))
+    }
+
+    pub fn write(&self, toolchain_dir: &Path) -> Result<()> {
+        let toml = ser::to_string_pretty(self)?;
+        file::write_file(&Self::path(toolchain_dir), &toml)?;
+        Ok(())
+    }
+
+    /// Returns the version of the component providing `executable`.
+    pub fn version_of(&self, executable: &str) -> Option<&Version> {
+        self.components
+            .values()
+            .find(|c| c.executables.iter().any(|e| e == executable))
+            .map(|c| &c.version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::target_triple::TargetTriple;
+
+    #[test]
+    fn manifest_round_trip() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        assert_eq!(ToolchainManifest::from_toolchain_dir(dir.path())?, None);
+
+        let cfg = DownloadCfg::new(
+            "forc",
+            TargetTriple::from_component("forc")?,
+            Some(Version::new(0, 46, 1)),
+        )?;
+        let mut manifest = ToolchainManifest::default();
+        manifest.components.insert(
+            cfg.name.clone(),
+            ComponentManifest::new(
+                &cfg,
+                dir.path().join("store").join("forc-0.46.1"),
+                vec!["forc".to_string(), "forc-fmt".to_string()],
+            ),
+        );
+        manifest.write(dir.path())?;
+
+        let read = ToolchainManifest::from_toolchain_dir(dir.path())?.unwrap();
+        assert_eq!(read, manifest);
+        assert_eq!(read.version_of("forc-fmt"), Some(&Version::new(0, 46, 1)));
+        assert_eq!(read.version_of("fuel-core"), None);
+        Ok(())
+    }
+}
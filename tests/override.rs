@@ -4,6 +4,7 @@ use std::str::FromStr;
 pub mod testcfg;
 use fuelup::{
     constants::FUEL_TOOLCHAIN_TOML_FILE,
+    fmt::format_toolchain_with_target,
     toolchain_override::{self, OverrideCfg, ToolchainCfg, ToolchainOverride},
 };
 use testcfg::FuelupState;
@@ -40,3 +41,164 @@ fn check_correct_forc_plugin_called() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn plus_toolchain_argument() -> Result<()> {
+    testcfg::setup(FuelupState::AllInstalled, &|cfg| {
+        cfg.fuelup(&["override", "set", "latest"]);
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.1.0\n");
+        assert_eq!(cfg.forc(&["+nightly", "--version"]).stdout, "forc 0.2.0\n");
+        assert_eq!(
+            cfg.forc(&["+nightly-2022-08-30", "wallet", "--version"])
+                .stdout,
+            "forc-wallet 0.2.0\n"
+        );
+
+        let output = cfg.forc(&["+beta-4", "--version"]);
+        assert_eq!(
+            output.stdout,
+            format!(
+                "toolchain '{}' is not installed; you may install it with 'fuelup toolchain install'\n",
+                format_toolchain_with_target("beta-4")
+            )
+        );
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_env_var() -> Result<()> {
+    testcfg::setup(FuelupState::AllInstalled, &|cfg| {
+        let toolchain_override = ToolchainOverride {
+            cfg: OverrideCfg::new(
+                ToolchainCfg {
+                    channel: toolchain_override::Channel::from_str("nightly-2022-08-30").unwrap(),
+                },
+                None,
+            ),
+            path: cfg.home.join(FUEL_TOOLCHAIN_TOML_FILE),
+        };
+        testcfg::setup_override_file(toolchain_override).unwrap();
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.2.0\n");
+
+        let env = [("FUELUP_TOOLCHAIN", "latest")];
+        let output = cfg.exec_with_env("forc", &["--version"], &env);
+        assert_eq!(output.stdout, "forc 0.1.0\n");
+
+        let latest = format_toolchain_with_target("latest");
+        let output = cfg.exec_with_env("fuelup", &["default"], &env);
+        assert_eq!(
+            output.stdout,
+            format!("{latest} (environment), {latest} (default)\n")
+        );
+
+        let output = cfg.exec_with_env("fuelup", &["show"], &env);
+        assert!(output.stdout.contains(&format!(
+            "{latest} (environment) (default), set by FUELUP_TOOLCHAIN"
+        )));
+
+        let output = cfg.exec_with_env("forc", &["--version"], &[("FUELUP_TOOLCHAIN", "beta-4")]);
+        assert_eq!(
+            output.stdout,
+            format!(
+                "toolchain '{}' set by FUELUP_TOOLCHAIN is not installed\n",
+                format_toolchain_with_target("beta-4")
+            )
+        );
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn directory_override_custom_toolchain() -> Result<()> {
+    testcfg::setup(FuelupState::LatestAndCustomInstalled, &|cfg| {
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.1.0\n");
+
+        let output = cfg.fuelup(&["override", "set", testcfg::CUSTOM_TOOLCHAIN_NAME]);
+        assert!(output.stdout.contains("override toolchain for"));
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.2.0\n");
+
+        let output = cfg.fuelup(&["override", "list"]);
+        assert_eq!(
+            output.stdout,
+            format!(
+                "{}\t{}\n",
+                cfg.home.display(),
+                testcfg::CUSTOM_TOOLCHAIN_NAME
+            )
+        );
+
+        cfg.fuelup(&["override", "unset"]);
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.1.0\n");
+        assert_eq!(cfg.fuelup(&["override", "list"]).stdout, "no overrides\n");
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn directory_override_precedes_toolchain_file() -> Result<()> {
+    testcfg::setup(FuelupState::AllInstalled, &|cfg| {
+        let toolchain_override = ToolchainOverride {
+            cfg: OverrideCfg::new(
+                ToolchainCfg {
+                    channel: toolchain_override::Channel::from_str("nightly-2022-08-30").unwrap(),
+                },
+                None,
+            ),
+            path: cfg.home.join(FUEL_TOOLCHAIN_TOML_FILE),
+        };
+        testcfg::setup_override_file(toolchain_override).unwrap();
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.2.0\n");
+
+        cfg.fuelup(&["override", "set", "latest"]);
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.1.0\n");
+
+        let output = cfg.fuelup(&["default"]);
+        let latest = format_toolchain_with_target("latest");
+        assert_eq!(
+            output.stdout,
+            format!("{latest} (directory override), {latest} (default)\n")
+        );
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn directory_override_nonexistent_toolchain() -> Result<()> {
+    testcfg::setup(FuelupState::LatestToolchainInstalled, &|cfg| {
+        let output = cfg.fuelup(&["override", "set", "no-such-toolchain"]);
+        assert_eq!(
+            output.stdout,
+            "Toolchain with name 'no-such-toolchain' does not exist\n"
+        );
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn directory_override_uninstalled_toolchain() -> Result<()> {
+    testcfg::setup(FuelupState::LatestAndCustomInstalled, &|cfg| {
+        cfg.fuelup(&["override", "set", testcfg::CUSTOM_TOOLCHAIN_NAME]);
+        std::fs::remove_dir_all(cfg.toolchains_dir().join(testcfg::CUSTOM_TOOLCHAIN_NAME)).unwrap();
+
+        let output = cfg.fuelup(&["show"]);
+        assert!(output.stdout.contains(&format!(
+            "toolchain '{}' set as the override for '{}' is not installed",
+            testcfg::CUSTOM_TOOLCHAIN_NAME,
+            cfg.home.display()
+        )));
+
+        let output = cfg.forc(&["--version"]);
+        assert!(output.stdout.contains(&format!(
+            "fuelup toolchain install {}",
+            testcfg::CUSTOM_TOOLCHAIN_NAME
+        )));
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use fuelup::fmt::format_toolchain_with_target;
+
+pub mod testcfg;
+use testcfg::FuelupState;
+
+#[test]
+fn fuelup_run() -> Result<()> {
+    testcfg::setup(FuelupState::AllInstalled, &|cfg| {
+        let output = cfg.fuelup(&["run", "nightly", "forc", "--version"]);
+        assert_eq!(output.stdout, "forc 0.2.0\n");
+
+        let output = cfg.fuelup(&["run", "nightly-2022-08-30", "forc-wallet", "--version"]);
+        assert_eq!(output.stdout, "forc-wallet 0.2.0\n");
+
+        let output = cfg.fuelup(&["run", "latest", "forc", "--version"]);
+        assert_eq!(output.stdout, "forc 0.1.0\n");
+
+        assert_eq!(
+            cfg.default_toolchain(),
+            Some(format_toolchain_with_target("latest"))
+        );
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn fuelup_run_not_installed() -> Result<()> {
+    testcfg::setup(FuelupState::LatestToolchainInstalled, &|cfg| {
+        let output = cfg.fuelup(&["run", "nightly", "forc", "--version"]);
+        assert_eq!(
+            output.stdout,
+            format!(
+                "toolchain '{}' is not installed; run with '--install' to install it first\n",
+                format_toolchain_with_target("nightly")
+            )
+        );
+
+        let output = cfg.fuelup(&["run", "--install", "my-toolchain", "forc", "--version"]);
+        assert_eq!(
+            output.stdout,
+            "custom toolchain 'my-toolchain' does not exist and cannot be installed\n"
+        );
+    })?;
+
+    Ok(())
+}
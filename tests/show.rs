@@ -325,3 +325,33 @@ latest-{target} (default)
     })?;
     Ok(())
 }
+
+#[test]
+fn fuelup_show_reads_manifest() -> Result<()> {
+    testcfg::setup(FuelupState::LatestToolchainInstalled, &|cfg| {
+        let target = TargetTriple::from_host().unwrap();
+        let toolchain_dir = cfg.toolchains_dir().join(format!("latest-{target}"));
+        std::fs::write(
+            toolchain_dir.join("manifest.toml"),
+            format!(
+                r#"[components.forc]
+version = "0.9.9"
+executables = ["forc", "forc-fmt"]
+store_path = "{}"
+installed_at = "2023-10-01T12:00:00Z"
+"#,
+                cfg.home.join(".fuelup/store/forc-0.9.9").display()
+            ),
+        )
+        .unwrap();
+
+        let stripped = strip_ansi_escapes::strip(cfg.fuelup(&["show"]).stdout);
+        let stdout = String::from_utf8_lossy(&stripped);
+        assert!(stdout.contains("  forc : 0.9.9\n"));
+        assert!(stdout.contains("    - forc-fmt : 0.9.9\n"));
+        // Executables missing from the manifest are still run to get their versions.
+        assert!(stdout.contains("    - forc-lsp : 0.1.0\n"));
+        assert!(stdout.contains("  fuel-core : 0.1.0\n"));
+    })?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use fuelup::fmt::format_toolchain_with_target;
+use std::fs;
+
+pub mod testcfg;
+use testcfg::FuelupState;
+
+#[test]
+fn fuelup_store_gc() -> Result<()> {
+    testcfg::setup(FuelupState::LatestToolchainInstalled, &|cfg| {
+        let store_dir = cfg.home.join(".fuelup").join("store");
+        for entry in ["forc-0.1.0", "fuel-core-0.1.0", "fuel-core-0.2.0"] {
+            fs::create_dir_all(store_dir.join(entry)).unwrap();
+        }
+        fs::hard_link(
+            cfg.toolchain_bin_dir(&format_toolchain_with_target("latest"))
+                .join("forc"),
+            store_dir.join("forc-0.1.0").join("forc"),
+        )
+        .unwrap();
+        fs::write(store_dir.join("fuel-core-0.1.0").join("fuel-core"), "").unwrap();
+        fs::create_dir(store_dir.join(".tmp-interrupted")).unwrap();
+
+        let project = cfg.home.join("my-project");
+        fs::create_dir(&project).unwrap();
+        fs::write(
+            project.join("fuel-toolchain.toml"),
+            "[toolchain]\nchannel = \"beta-3\"\n\n[components]\nfuel-core = \"0.2.0\"\n",
+        )
+        .unwrap();
+
+        let output = cfg.fuelup(&["store", "gc", "--dry-run"]);
+        assert!(output.stdout.contains("Would remove fuel-core-0.1.0"));
+        assert!(output.stdout.contains("Would remove fuel-core-0.2.0"));
+        assert!(!output.stdout.contains("forc-0.1.0"));
+        assert!(store_dir.join("fuel-core-0.1.0").exists());
+
+        let output = cfg.fuelup(&["store", "gc", "--project", project.to_str().unwrap()]);
+        assert!(output.stdout.contains("Removed fuel-core-0.1.0"));
+        assert!(output.stdout.contains("Removed .tmp-interrupted"));
+        assert!(output.stdout.contains("Reclaimed"));
+        assert!(store_dir.join("forc-0.1.0").exists());
+        assert!(!store_dir.join("fuel-core-0.1.0").exists());
+        assert!(store_dir.join("fuel-core-0.2.0").exists());
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn fuelup_store_list() -> Result<()> {
+    testcfg::setup(FuelupState::LatestToolchainInstalled, &|cfg| {
+        let latest = format_toolchain_with_target("latest");
+        let store_dir = cfg.home.join(".fuelup").join("store");
+        fs::create_dir_all(store_dir.join("forc-0.1.0")).unwrap();
+        fs::create_dir_all(store_dir.join("fuel-core-0.1.0")).unwrap();
+        fs::hard_link(
+            cfg.toolchain_bin_dir(&latest).join("forc"),
+            store_dir.join("forc-0.1.0").join("forc"),
+        )
+        .unwrap();
+        fs::write(store_dir.join("forc-0.1.0").join("fuels_version"), "0.41.0").unwrap();
+
+        let output = cfg.fuelup(&["store", "list"]);
+        assert!(output.stdout.contains("forc-0.1.0"));
+        assert!(output.stdout.contains("fuels version: 0.41.0"));
+        assert!(output.stdout.contains(&format!("toolchains: {latest}")));
+        assert!(output.stdout.contains("fuel-core-0.1.0"));
+        assert!(output.stdout.contains("toolchains: none"));
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn fuelup_store_verify() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let store_dir = cfg.home.join(".fuelup").join("store");
+        fs::create_dir_all(store_dir.join("fuel-core-0.1.0")).unwrap();
+
+        let output = cfg.fuelup(&["store", "verify"]);
+        assert!(output.stdout.contains("fuel-core-0.1.0: broken"));
+        assert!(output.stdout.contains("missing executable 'fuel-core'"));
+        assert!(output.stdout.contains("fuelup store verify --repair"));
+    })?;
+
+    Ok(())
+}
@@ -117,10 +117,21 @@ impl TestCfg {
     /// This invokes std::process::Command with some default environment variables
     /// set up nicely for testing fuelup and its managed binaries.
     pub fn exec(&mut self, proc_name: &str, args: &[&str]) -> TestOutput {
+        self.exec_with_env(proc_name, args, &[])
+    }
+
+    /// Same as testcfg::exec(), but with additional environment variables set for the command.
+    pub fn exec_with_env(
+        &mut self,
+        proc_name: &str,
+        args: &[&str],
+        envs: &[(&str, &str)],
+    ) -> TestOutput {
         let path = self.fuelup_bin_dirpath.join(proc_name);
         let output = Command::new(path)
             .args(args)
             .current_dir(&self.home)
+            .env_remove("FUELUP_HOME")
             .env("HOME", &self.home)
             .env("CARGO_HOME", &self.home.join(".cargo"))
             .env(
@@ -133,6 +144,7 @@ impl TestCfg {
                 ),
             )
             .env("TERM", "dumb")
+            .envs(envs.iter().copied())
             .output()
             .expect("Failed to execute command");
         let stdout = String::from_utf8(output.stdout).unwrap();
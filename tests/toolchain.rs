@@ -77,7 +77,7 @@ fn fuelup_toolchain_install_malformed_date() -> Result<()> {
         let toolchain = "nightly-2022-08-31-";
         let output = cfg.fuelup(&["toolchain", "install", toolchain]);
 
-        let expected_stdout = format!("Invalid toolchain metadata within input '{toolchain}' - You specified target '': specifying a target is not supported yet.\n");
+        let expected_stdout = format!("Invalid toolchain metadata within input '{toolchain}' - Invalid target '': missing vendor-os specifier\n");
 
         assert!(output.status.success());
         assert_eq!(output.stdout, expected_stdout);
@@ -87,16 +87,23 @@ fn fuelup_toolchain_install_malformed_date() -> Result<()> {
 }
 
 #[test]
-fn fuelup_toolchain_install_date_target_disallowed() -> Result<()> {
+fn fuelup_toolchain_install_date_target() -> Result<()> {
     testcfg::setup(FuelupState::Empty, &|cfg| {
-        let toolchain = "nightly-2022-08-31-x86_64-apple-darwin";
-        let output = cfg.fuelup(&["toolchain", "install", toolchain]);
+        // Install for a target other than the host, as when pre-staging toolchains.
+        let target = match TargetTriple::from_host().unwrap().to_string().as_str() {
+            "x86_64-unknown-linux-gnu" => "aarch64-unknown-linux-gnu",
+            _ => "x86_64-unknown-linux-gnu",
+        };
+        let toolchain = format!("nightly-2022-08-31-{target}");
+        cfg.fuelup(&["toolchain", "install", &toolchain]);
 
-        let expected_stdout =
-            format!("Invalid toolchain metadata within input '{toolchain}' - You specified target 'x86_64-apple-darwin': specifying a target is not supported yet.\n");
+        for entry in cfg.toolchains_dir().read_dir().expect("Could not read dir") {
+            let toolchain_dir = entry.unwrap();
+            assert_eq!(toolchain, toolchain_dir.file_name().to_str().unwrap());
+            assert!(toolchain_dir.file_type().unwrap().is_dir());
 
-        assert!(output.status.success());
-        assert_eq!(output.stdout, expected_stdout);
+            expect_files_exist(&toolchain_dir.path().join("bin"), ALL_BINS);
+        }
     })?;
 
     Ok(())
@@ -159,6 +166,44 @@ default toolchain set to '{CUSTOM_TOOLCHAIN_NAME}'\n"
     Ok(())
 }
 
+#[test]
+fn fuelup_toolchain_link() -> Result<()> {
+    testcfg::setup(FuelupState::LatestToolchainInstalled, &|cfg| {
+        use std::os::unix::fs::PermissionsExt;
+
+        let build_dir = cfg.home.join("sway").join("target").join("release");
+        std::fs::create_dir_all(&build_dir).unwrap();
+        let forc = build_dir.join("forc");
+        std::fs::write(&forc, "#!/bin/sh\necho \"forc 0.99.0\"\n").unwrap();
+        std::fs::set_permissions(&forc, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let output = cfg.fuelup(&["toolchain", "link", "my-dev", build_dir.to_str().unwrap()]);
+        assert!(output.stdout.starts_with("Linked toolchain 'my-dev'"));
+        assert_eq!(cfg.forc(&["+my-dev", "--version"]).stdout, "forc 0.99.0\n");
+
+        cfg.fuelup(&["default", "my-dev"]);
+        assert_eq!(cfg.forc(&["--version"]).stdout, "forc 0.99.0\n");
+
+        let output = cfg.fuelup(&["component", "add", "fuel-core"]);
+        assert!(output
+            .stdout
+            .starts_with("Installing components is not supported for 'my-dev'"));
+        assert!(!build_dir.join("fuel-core").exists());
+
+        let output = cfg.fuelup(&["toolchain", "link", "my-dev", build_dir.to_str().unwrap()]);
+        assert_eq!(
+            output.stdout,
+            "Toolchain with name 'my-dev' already exists\n"
+        );
+
+        cfg.fuelup(&["toolchain", "uninstall", "my-dev"]);
+        assert!(!cfg.toolchains_dir().join("my-dev").exists());
+        assert!(forc.exists());
+    })?;
+
+    Ok(())
+}
+
 #[test]
 fn fuelup_toolchain_new_disallowed() -> Result<()> {
     testcfg::setup(FuelupState::Empty, &|cfg| {
@@ -184,3 +229,73 @@ fn fuelup_toolchain_new_disallowed_with_target() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn fuelup_toolchain_new_fuelup_home() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let fuelup_home = cfg.home.join("custom-fuelup-home");
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "new", CUSTOM_TOOLCHAIN_NAME],
+            &[("FUELUP_HOME", fuelup_home.to_str().unwrap())],
+        );
+        assert!(output.status.success());
+
+        assert!(fuelup_home
+            .join("toolchains")
+            .join(CUSTOM_TOOLCHAIN_NAME)
+            .join("bin")
+            .is_dir());
+        assert!(fuelup_home.join("settings.toml").is_file());
+        assert!(!cfg.toolchain_bin_dir(CUSTOM_TOOLCHAIN_NAME).exists());
+        assert_eq!(cfg.default_toolchain(), None);
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_channel_file_disallowed_name() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let channel_file = cfg.home.join("my-channel.toml");
+        std::fs::write(&channel_file, "[pkg]\n").unwrap();
+
+        for toolchain in [channel::LATEST, channel::NIGHTLY] {
+            let output = cfg.fuelup(&[
+                "toolchain",
+                "install",
+                "--channel-file",
+                channel_file.to_str().unwrap(),
+                toolchain,
+            ]);
+            let expected_stdout = format!(
+                "Cannot use distributable toolchain name '{toolchain}' as a custom toolchain name\n"
+            );
+            assert_eq!(output.stdout, expected_stdout);
+        }
+        assert!(cfg.toolchains_dir().read_dir().unwrap().next().is_none());
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn fuelup_toolchain_install_offline_without_cache() -> Result<()> {
+    testcfg::setup(FuelupState::Empty, &|cfg| {
+        let output = cfg.fuelup(&["--offline", "toolchain", "install", "latest"]);
+        assert!(output
+            .stdout
+            .contains("Could not build download configs from channel: No cached copy of channel"));
+        assert!(output.stdout.contains("is available in offline mode"));
+        assert!(cfg.toolchains_dir().read_dir().unwrap().next().is_none());
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["toolchain", "install", "latest"],
+            &[("FUELUP_OFFLINE", "1")],
+        );
+        assert!(output.stdout.contains("is available in offline mode"));
+    })?;
+
+    Ok(())
+}
@@ -75,3 +75,17 @@ fn fuelup_update_conflict() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn fuelup_update_rollback_without_previous() -> Result<()> {
+    testcfg::setup(FuelupState::LatestToolchainInstalled, &|cfg| {
+        let output = cfg.fuelup(&["update", "--rollback", "latest"]);
+        let expected_stdout = format!(
+            "toolchain 'latest-{}' has no previous installation to roll back to\n",
+            TargetTriple::from_host().unwrap()
+        );
+        assert_eq!(output.stdout, expected_stdout);
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use std::str::FromStr;
+
+pub mod testcfg;
+use fuelup::{
+    constants::FUEL_TOOLCHAIN_TOML_FILE,
+    fmt::format_toolchain_with_target,
+    toolchain_override::{self, OverrideCfg, ToolchainCfg, ToolchainOverride},
+};
+use testcfg::FuelupState;
+
+#[test]
+fn fuelup_which() -> Result<()> {
+    testcfg::setup(FuelupState::AllInstalled, &|cfg| {
+        let latest = format_toolchain_with_target("latest");
+        let output = cfg.fuelup(&["which", "forc"]);
+        assert_eq!(
+            output.stdout,
+            format!(
+                "{}\n  toolchain: {latest}\n  selected by: default toolchain\n",
+                cfg.toolchain_bin_dir(&latest).join("forc").display()
+            )
+        );
+
+        let output = cfg.fuelup(&["which", "forc", "fmt"]);
+        assert_eq!(output.stdout, cfg.fuelup(&["which", "forc-fmt"]).stdout,);
+        assert!(output.stdout.starts_with(&format!(
+            "{}\n",
+            cfg.toolchain_bin_dir(&latest).join("forc-fmt").display()
+        )));
+
+        let toolchain_override = ToolchainOverride {
+            cfg: OverrideCfg::new(
+                ToolchainCfg {
+                    channel: toolchain_override::Channel::from_str("nightly-2022-08-30").unwrap(),
+                },
+                None,
+            ),
+            path: cfg.home.join(FUEL_TOOLCHAIN_TOML_FILE),
+        };
+        testcfg::setup_override_file(toolchain_override).unwrap();
+
+        let nightly_date = format_toolchain_with_target("nightly-2022-08-30");
+        let output = cfg.fuelup(&["which", "forc"]);
+        assert_eq!(
+            output.stdout,
+            format!(
+                "{}\n  toolchain: {nightly_date}\n  selected by: override file {}\n",
+                cfg.toolchain_bin_dir(&nightly_date).join("forc").display(),
+                cfg.home.join(FUEL_TOOLCHAIN_TOML_FILE).display()
+            )
+        );
+
+        let output = cfg.exec_with_env(
+            "fuelup",
+            &["which", "forc"],
+            &[("FUELUP_TOOLCHAIN", "latest")],
+        );
+        assert!(output
+            .stdout
+            .ends_with("  selected by: FUELUP_TOOLCHAIN environment variable\n"));
+    })?;
+
+    Ok(())
+}